@@ -4,12 +4,6 @@
 //! RESEND_API_KEY=re_your_api_key cargo run --example custom-config
 //! ```
 
-#[cfg(feature = "blocking")]
-fn main() {
-    panic!("This example is meant for async mode.");
-}
-
-#[cfg(not(feature = "blocking"))]
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     use anyhow::Context;
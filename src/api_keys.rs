@@ -1,11 +1,12 @@
 use std::fmt;
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::{Config, Result};
 use crate::{
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::{ApiKey, ApiKeyToken, CreateApiKeyOptions, UpdateApiKeyOptions, UpdateApiKeyResponse},
 };
 
@@ -13,15 +14,17 @@ use crate::{
 #[derive(Clone)]
 pub struct ApiKeysSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl ApiKeysSvc {
     /// Add a new API key to authenticate communications with Resend.
     ///
     /// <https://resend.com/docs/api-reference/api-keys/create-api-key>
-    #[maybe_async::maybe_async]
     pub async fn create(&self, api_key: CreateApiKeyOptions) -> Result<ApiKeyToken> {
         let request = self.0.build(Method::POST, "/api-keys");
-        let response = self.0.send(request.json(&api_key)).await?;
-        let content = response.json::<ApiKeyToken>().await?;
+        let content = self
+            .0
+            .send_json::<ApiKeyToken>(request.json(&api_key))
+            .await?;
 
         Ok(content)
     }
@@ -31,22 +34,26 @@ impl ApiKeysSvc {
     /// - Default limit: no limit (return everything)
     ///
     /// <https://resend.com/docs/api-reference/api-keys/list-api-keys>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<ApiKey>> {
         let request = self.0.build(Method::GET, "/api-keys").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<ApiKey>>().await?;
+        let content = self.0.send_json::<ListResponse<ApiKey>>(request).await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<ApiKey> {
+        Paginator::new(Arc::clone(&self.0), "/api-keys".to_owned(), &list_opts)
+    }
+
     /// Updates an existing API key.
     ///
     /// Only the name of the API key can be changed. Permission and domain access are fixed at
     /// creation time and cannot be widened after the fact.
     ///
     /// <https://resend.com/docs/api-reference/api-keys/update-api-key>
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         api_key_id: &str,
@@ -55,8 +62,10 @@ impl ApiKeysSvc {
         let path = format!("/api-keys/{api_key_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&changes)).await?;
-        let content = response.json::<UpdateApiKeyResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateApiKeyResponse>(request.json(&changes))
+            .await?;
 
         Ok(content)
     }
@@ -64,7 +73,6 @@ impl ApiKeysSvc {
     /// Remove an existing API key.
     ///
     /// <https://resend.com/docs/api-reference/api-keys/delete-api-key>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, api_key_id: &str) -> Result<()> {
         let path = format!("/api-keys/{api_key_id}");
 
@@ -212,7 +220,6 @@ pub mod types {
 #[cfg(test)]
 #[allow(clippy::needless_return)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -221,7 +228,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
 
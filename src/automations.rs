@@ -1,14 +1,16 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::{
         Automation, AutomationMinimal, AutomationRun, CreateAutomationOptions,
         CreateAutomationResponse, DeleteAutomationResponse, DuplicateAutomationResponse,
-        StopAutomationResponse, UpdateAutomationOptions, UpdateAutomationResponse,
+        ListAutomationRunsOptions, StopAutomationResponse, UpdateAutomationOptions,
+        UpdateAutomationResponse,
     },
 };
 
@@ -16,18 +18,20 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct AutomationsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl AutomationsSvc {
     /// Create a new automation to automate email sequences.
     ///
     /// <https://resend.com/docs/api-reference/automations/create-automation>
-    #[maybe_async::maybe_async]
     pub async fn create(
         &self,
         automation: CreateAutomationOptions,
     ) -> Result<CreateAutomationResponse> {
         let request = self.0.build(Method::POST, "/automations");
-        let response = self.0.send(request.json(&automation)).await?;
-        let content = response.json::<CreateAutomationResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateAutomationResponse>(request.json(&automation))
+            .await?;
 
         Ok(content)
     }
@@ -35,7 +39,6 @@ impl AutomationsSvc {
     /// Update an existing automation.
     ///
     /// <https://resend.com/docs/api-reference/automations/update-automation>
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         automation_id: &str,
@@ -44,8 +47,10 @@ impl AutomationsSvc {
         let path = format!("/automations/{automation_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateAutomationResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateAutomationResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -53,13 +58,11 @@ impl AutomationsSvc {
     /// Retrieve a single automation.
     ///
     /// <https://resend.com/docs/api-reference/automations/get-automation>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, automation_id: &str) -> Result<Automation> {
         let path = format!("/automations/{automation_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Automation>().await?;
+        let content = self.0.send_json::<Automation>(request).await?;
 
         Ok(content)
     }
@@ -67,28 +70,34 @@ impl AutomationsSvc {
     /// Retrieve a list of automations.
     ///
     /// <https://resend.com/docs/api-reference/automations/list-automations>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(
         &self,
         list_opts: ListOptions<T>,
     ) -> Result<ListResponse<AutomationMinimal>> {
         let request = self.0.build(Method::GET, "/automations").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<AutomationMinimal>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<AutomationMinimal>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<AutomationMinimal> {
+        Paginator::new(Arc::clone(&self.0), "/automations".to_owned(), &list_opts)
+    }
+
     /// Stop a running automation.
     ///
     /// <https://resend.com/docs/api-reference/automations/stop-automation>
-    #[maybe_async::maybe_async]
     pub async fn stop(&self, automation_id: &str) -> Result<StopAutomationResponse> {
         let path = format!("/automations/{automation_id}/stop");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<StopAutomationResponse>().await?;
+        let content = self.0.send_json::<StopAutomationResponse>(request).await?;
 
         Ok(content)
     }
@@ -96,13 +105,14 @@ impl AutomationsSvc {
     /// Duplicate an existing automation.
     ///
     /// <https://resend.com/docs/api-reference/automations/duplicate-automation>
-    #[maybe_async::maybe_async]
     pub async fn duplicate(&self, automation_id: &str) -> Result<DuplicateAutomationResponse> {
         let path = format!("/automations/{automation_id}/duplicate");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DuplicateAutomationResponse>().await?;
+        let content = self
+            .0
+            .send_json::<DuplicateAutomationResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -110,13 +120,14 @@ impl AutomationsSvc {
     /// Remove an existing automation.
     ///
     /// <https://resend.com/docs/api-reference/automations/delete-automation>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, automation_id: &str) -> Result<DeleteAutomationResponse> {
         let path = format!("/automations/{automation_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DeleteAutomationResponse>().await?;
+        let content = self
+            .0
+            .send_json::<DeleteAutomationResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -124,36 +135,43 @@ impl AutomationsSvc {
     /// Retrieve a list of automation runs.
     ///
     /// <https://resend.com/docs/api-reference/automations/list-automation-runs>
-    #[maybe_async::maybe_async]
     pub async fn list_runs<T>(
         &self,
         automation_id: &str,
-        status_filter: Option<String>,
-        list_opts: ListOptions<T>,
+        list_opts: impl Into<ListAutomationRunsOptions<T>>,
     ) -> Result<ListResponse<AutomationRun>> {
         let path = format!("/automations/{automation_id}/runs");
 
-        let request = self
+        let request = self.0.build(Method::GET, &path).query(&list_opts.into());
+        let content = self
             .0
-            .build(Method::GET, &path)
-            .query(&list_opts)
-            .query(&status_filter);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<AutomationRun>>().await?;
+            .send_json::<ListResponse<AutomationRun>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list_runs`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_runs_all<T>(
+        &self,
+        automation_id: &str,
+        list_opts: impl Into<ListAutomationRunsOptions<T>>,
+    ) -> Paginator<AutomationRun> {
+        let path = format!("/automations/{automation_id}/runs");
+
+        Paginator::new(Arc::clone(&self.0), path, &list_opts.into())
+    }
+
     /// Retrieve a single automation run.
     ///
     /// <https://resend.com/docs/api-reference/automations/get-automation-run>
-    #[maybe_async::maybe_async]
     pub async fn get_run(&self, automation_id: &str, run_id: &str) -> Result<AutomationRun> {
         let path = format!("/automations/{automation_id}/runs/{run_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<AutomationRun>().await?;
+        let content = self.0.send_json::<AutomationRun>(request).await?;
 
         Ok(content)
     }
@@ -166,6 +184,8 @@ pub mod types {
     use serde::{Deserialize, Serialize};
     use serde_json::Value;
 
+    use crate::list_opts::{FilteredListOptions, TimeNotSpecified};
+
     crate::define_id_type!(AutomationId);
     crate::define_id_type!(AutomationRunId);
 
@@ -468,6 +488,13 @@ pub mod types {
         trigger: Option<AutomationRunTrigger>,
     }
 
+    impl crate::list_opts::ListItem for AutomationRun {
+        #[inline]
+        fn list_id(&self) -> &str {
+            self.id.as_ref()
+        }
+    }
+
     #[must_use]
     #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
     #[serde(rename_all = "snake_case")]
@@ -478,6 +505,32 @@ pub mod types {
         Cancelled,
     }
 
+    /// Typed filters of [`crate::automations::AutomationsSvc::list_runs`], see
+    /// [`ListAutomationRunsOptions`].
+    #[must_use]
+    #[derive(Debug, Clone, Copy, Default, Serialize)]
+    pub struct AutomationRunsFilter {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        status: Option<AutomationRunStatus>,
+    }
+
+    /// Query parameters for [`crate::automations::AutomationsSvc::list_runs`].
+    ///
+    /// Note that [`ListAutomationRunsOptions::default()`] applies no filters.
+    pub type ListAutomationRunsOptions<List = TimeNotSpecified> =
+        FilteredListOptions<AutomationRunsFilter, List>;
+
+    impl<List> ListAutomationRunsOptions<List> {
+        /// Only lists the runs whose status is `status`, sent as the `status` query parameter.
+        ///
+        /// <https://resend.com/docs/api-reference/automations/list-automation-runs>
+        #[inline]
+        pub const fn with_status(mut self, status: AutomationRunStatus) -> Self {
+            self.filter.status = Some(status);
+            self
+        }
+    }
+
     #[must_use]
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct AutomationRunTrigger {
@@ -496,7 +549,6 @@ mod test {
         types::{Automation, AutomationStatus, Connection, CreateAutomationOptions, Step},
     };
 
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -577,7 +629,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
 
@@ -610,7 +661,7 @@ mod test {
         // List Runs
         let runs = resend
             .automations
-            .list_runs(&automation.id, None, ListOptions::default())
+            .list_runs(&automation.id, ListOptions::default())
             .await?;
         assert!(runs.data.is_empty());
 
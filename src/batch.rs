@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::{
@@ -7,6 +8,7 @@ use crate::{
     batch::types::BatchValidation,
     emails::types::CreateEmailBaseOptions,
     idempotent::Idempotent,
+    meta::WithMeta,
     types::{CreateEmailResponse, SendEmailBatchPermissiveResponse},
 };
 
@@ -14,6 +16,7 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct BatchSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl BatchSvc {
     /// Trigger up to 100 batch emails at once.
     ///
@@ -21,7 +24,6 @@ impl BatchSvc {
     /// that permits you to send up to 100 emails in a single API call.
     ///
     /// <https://resend.com/docs/api-reference/emails/send-batch-emails>
-    #[maybe_async::maybe_async]
     pub async fn send<T>(
         &self,
         emails: impl Into<Idempotent<T>>,
@@ -29,14 +31,27 @@ impl BatchSvc {
     where
         T: IntoIterator<Item = CreateEmailBaseOptions> + Send,
     {
-        Ok(self
-            .send_with_batch_validation(emails, BatchValidation::default())
-            .await?
-            .data)
+        Ok(self.send_with_meta(emails).await?.data)
+    }
+
+    /// The same as [`BatchSvc::send`] but also returns the [`ResponseMeta`] of the response.
+    ///
+    /// [`ResponseMeta`]: crate::meta::ResponseMeta
+    pub async fn send_with_meta<T>(
+        &self,
+        emails: impl Into<Idempotent<T>>,
+    ) -> Result<WithMeta<Vec<CreateEmailResponse>>>
+    where
+        T: IntoIterator<Item = CreateEmailBaseOptions> + Send,
+    {
+        let response = self
+            .send_batch(emails.into(), BatchValidation::default())
+            .await?;
+
+        Ok(response.map(|response| response.data))
     }
 
     /// The same as [`BatchSvc::send`] but allows you to specify a [`BatchValidation`] mode.
-    #[maybe_async::maybe_async]
     pub async fn send_with_batch_validation<T>(
         &self,
         emails: impl Into<Idempotent<T>>,
@@ -45,8 +60,25 @@ impl BatchSvc {
     where
         T: IntoIterator<Item = CreateEmailBaseOptions> + Send,
     {
-        let emails: Idempotent<T> = emails.into();
+        Ok(self.send_batch(emails.into(), batch_validation).await?.data)
+    }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "resend.batch.send",
+            skip_all,
+            fields(%batch_validation, emails, recipients, attachments, attachment_bytes)
+        )
+    )]
+    async fn send_batch<T>(
+        &self,
+        emails: Idempotent<T>,
+        batch_validation: BatchValidation,
+    ) -> Result<WithMeta<SendEmailBatchPermissiveResponse>>
+    where
+        T: IntoIterator<Item = CreateEmailBaseOptions> + Send,
+    {
         let mut request = self.0.build(Method::POST, "/emails/batch");
 
         request = request.header("x-batch-validation", batch_validation.to_string());
@@ -57,10 +89,39 @@ impl BatchSvc {
 
         let emails: Vec<_> = emails.data.into_iter().collect();
 
-        let response = self.0.send(request.json(&emails)).await?;
-        let content = response.json::<SendEmailBatchPermissiveResponse>().await?;
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            let _span = span
+                .record("emails", emails.len())
+                .record(
+                    "recipients",
+                    emails
+                        .iter()
+                        .map(CreateEmailBaseOptions::recipient_count)
+                        .sum::<usize>(),
+                )
+                .record(
+                    "attachments",
+                    emails
+                        .iter()
+                        .map(CreateEmailBaseOptions::attachment_count)
+                        .sum::<usize>(),
+                )
+                .record(
+                    "attachment_bytes",
+                    emails
+                        .iter()
+                        .map(CreateEmailBaseOptions::attachment_bytes)
+                        .sum::<usize>(),
+                );
+        }
+
+        for email in &emails {
+            email.check_attachments_size()?;
+        }
 
-        Ok(content)
+        self.0.send_json_with_meta(request.json(&emails)).await
     }
 }
 
@@ -120,7 +181,6 @@ pub mod types {
 
 #[cfg(test)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         test::{CLIENT, DebugResult},
         types::{
@@ -131,7 +191,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     #[allow(clippy::unwrap_used, clippy::indexing_slicing)]
     async fn strict_error() -> DebugResult<()> {
         let resend = &*CLIENT;
@@ -165,7 +224,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     #[allow(clippy::unwrap_used, clippy::indexing_slicing)]
     async fn permissive_error() -> DebugResult<()> {
         let resend = &*CLIENT;
@@ -207,7 +265,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     #[allow(clippy::unwrap_used, clippy::indexing_slicing)]
     async fn permissive_ok() -> DebugResult<()> {
         let resend = &*CLIENT;
@@ -245,7 +302,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     #[allow(clippy::unwrap_used, clippy::indexing_slicing)]
     async fn strict_ok() -> DebugResult<()> {
         let resend = &*CLIENT;
@@ -277,7 +333,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn template() -> DebugResult<()> {
         use std::collections::HashMap;
 
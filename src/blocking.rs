@@ -0,0 +1,408 @@
+//! Blocking client that can be used alongside the async one.
+//!
+//! With the `blocking-client` feature, [`blocking::Resend`](Resend) exposes the same services as
+//! [`crate::Resend`] with synchronous methods, so both clients can be used in the same build. Every
+//! call is run to completion on a small runtime owned by the client.
+//!
+//! `blocking-client` is purely additive and never changes the async API. The `blocking` feature is
+//! a deprecated alias of it.
+//!
+//! ### Example
+//!
+//! ```rust,no_run
+//! use resend_rs::blocking::Resend;
+//! use resend_rs::types::CreateEmailBaseOptions;
+//!
+//! let resend = Resend::new("re_...");
+//!
+//! let email = CreateEmailBaseOptions::new("Acme <onboarding@a.dev>", ["delivered@resend.dev"], "Hi")
+//!     .with_text("Hello World!");
+//! let id = resend.emails.send(email)?.id;
+//! # Ok::<(), resend_rs::Error>(())
+//! ```
+//!
+//! ### Notes
+//!
+//! The methods block the current thread and must not be called from within an async runtime,
+//! use [`crate::Resend`] there instead.
+
+pub use client::Resend;
+pub use paginator::Paginator;
+
+use crate::{Error, Result, rate_limit::RetryOptions};
+
+/// Blocking version of [`rate_limit::send_with_retry_opts`](crate::rate_limit::send_with_retry_opts):
+/// calls `f` again while it fails with [`Error::RateLimit`], waiting as specified by `opts`.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use resend_rs::{
+///   blocking::{send_with_retry_opts, Resend},
+///   list_opts::ListOptions,
+///   rate_limit::RetryOptions,
+/// };
+///
+/// let resend = Resend::default();
+///
+/// let retry_opts = RetryOptions::default();
+/// let response = send_with_retry_opts(|| resend.api_keys.list(ListOptions::default()), &retry_opts);
+/// assert!(response.is_ok());
+/// ```
+pub fn send_with_retry_opts<B>(f: impl Fn() -> Result<B>, opts: &RetryOptions) -> Result<B> {
+    let mut retries = 0;
+
+    loop {
+        match f() {
+            Err(Error::RateLimit {
+                ratelimit_reset, ..
+            }) if retries < opts.max_retries => {
+                std::thread::sleep(opts.delay(ratelimit_reset));
+                retries += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Same as [`send_with_retry_opts`] but uses [`RetryOptions::default`].
+pub fn send_with_retry<B>(f: impl Fn() -> Result<B>) -> Result<B> {
+    send_with_retry_opts(f, &RetryOptions::default())
+}
+
+mod client {
+    use std::{fmt, sync::Arc};
+
+    use super::services::{
+        ApiKeysSvc, AutomationsSvc, BatchSvc, BroadcastsSvc, ContactsSvc, DomainsSvc, EmailsSvc,
+        EventsSvc, LogsSvc, OAuthSvc, ReceivingSvc, SegmentsSvc, SuppressionsSvc, TemplateSvc,
+        TopicsSvc, WebhookSvc,
+    };
+    use crate::Config;
+
+    /// The blocking [Resend](https://resend.com) client.
+    ///
+    /// See the [module level documentation](super) for details.
+    #[must_use]
+    #[derive(Clone)]
+    pub struct Resend {
+        /// `Resend` APIs for `/emails` endpoints.
+        pub emails: EmailsSvc,
+        /// `Resend` APIs for the batch `/emails` endpoints.
+        pub batch: BatchSvc,
+        /// `Resend` APIs for `/api-keys` endpoints.
+        pub api_keys: ApiKeysSvc,
+        /// `Resend` APIs for `/audiences` endpoints.
+        pub segments: SegmentsSvc,
+        /// `Resend` APIs for `/audiences/:id/contacts` endpoints.
+        pub contacts: ContactsSvc,
+        /// `Resend` APIs for `/domains` endpoints.
+        pub domains: DomainsSvc,
+        /// `Resend` APIs for `/broadcasts` endpoints.
+        pub broadcasts: BroadcastsSvc,
+        /// `Resend` APIs for `/templates` endpoints.
+        pub templates: TemplateSvc,
+        /// `Resend` APIs for `/topics` endpoints.
+        pub topics: TopicsSvc,
+        /// `Resend` APIs for `/emails/receiving` endpoints.
+        pub receiving: ReceivingSvc,
+        /// `Resend` APIs for `/webhooks` endpoints.
+        pub webhooks: WebhookSvc,
+        /// `Resend` APIs for `/logs` endpoints.
+        pub logs: LogsSvc,
+        /// `Resend` APIs for `/automations` endpoints.
+        pub automations: AutomationsSvc,
+        /// `Resend` APIs for `/events` endpoints.
+        pub events: EventsSvc,
+        /// `Resend` APIs for `/oauth` endpoints.
+        pub oauth: OAuthSvc,
+        /// `Resend` APIs for `/suppressions` endpoints.
+        pub suppressions: SuppressionsSvc,
+        inner: crate::Resend,
+    }
+
+    impl Resend {
+        /// Creates a new blocking [`Resend`] client.
+        ///
+        /// ### Panics
+        ///
+        /// - Panics if the environment variable `RESEND_BASE_URL` is set but is not a valid `URL`.
+        /// - Panics if the environment variable `RESEND_RATE_LIMIT` is set but is not a valid non
+        ///   zero `u32`.
+        /// - Panics if the runtime cannot be created.
+        pub fn new(api_key: &str) -> Self {
+            Self::from_async(crate::Resend::new(api_key))
+        }
+
+        /// Creates a new blocking [`Resend`] client with a provided [`Config`].
+        ///
+        /// Use [`ConfigBuilder::new`](crate::ConfigBuilder::new) to construct a [`Config`]
+        /// instance.
+        ///
+        /// ### Panics
+        ///
+        /// - Panics if the runtime cannot be created.
+        pub fn with_config(config: Config) -> Self {
+            Self::from_async(crate::Resend::with_config(config))
+        }
+
+        /// Creates a new blocking [`Resend`] client sharing the configuration (and therefore the
+        /// rate limit) of an async [`crate::Resend`] client.
+        ///
+        /// ### Panics
+        ///
+        /// - Panics if the runtime cannot be created.
+        pub fn from_async(resend: crate::Resend) -> Self {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("resend-blocking")
+                .enable_all()
+                .build()
+                .expect("blocking client runtime should be created");
+            let runtime = Arc::new(runtime);
+
+            Self {
+                emails: EmailsSvc(resend.emails.clone(), Arc::clone(&runtime)),
+                batch: BatchSvc(resend.batch.clone(), Arc::clone(&runtime)),
+                api_keys: ApiKeysSvc(resend.api_keys.clone(), Arc::clone(&runtime)),
+                segments: SegmentsSvc(resend.segments.clone(), Arc::clone(&runtime)),
+                contacts: ContactsSvc(resend.contacts.clone(), Arc::clone(&runtime)),
+                domains: DomainsSvc(resend.domains.clone(), Arc::clone(&runtime)),
+                broadcasts: BroadcastsSvc(resend.broadcasts.clone(), Arc::clone(&runtime)),
+                templates: TemplateSvc(resend.templates.clone(), Arc::clone(&runtime)),
+                topics: TopicsSvc(resend.topics.clone(), Arc::clone(&runtime)),
+                receiving: ReceivingSvc(resend.receiving.clone(), Arc::clone(&runtime)),
+                webhooks: WebhookSvc(resend.webhooks.clone(), Arc::clone(&runtime)),
+                logs: LogsSvc(resend.logs.clone(), Arc::clone(&runtime)),
+                automations: AutomationsSvc(resend.automations.clone(), Arc::clone(&runtime)),
+                events: EventsSvc(resend.events.clone(), Arc::clone(&runtime)),
+                oauth: OAuthSvc(resend.oauth.clone(), Arc::clone(&runtime)),
+                suppressions: SuppressionsSvc(resend.suppressions.clone(), runtime),
+                inner: resend,
+            }
+        }
+
+        /// Creates a new blocking [`Resend`] client from the environment variables, see
+        /// [`crate::Resend::try_default`].
+        ///
+        /// ### Panics
+        ///
+        /// - Panics if the runtime cannot be created.
+        pub fn try_default() -> Result<Self, crate::ConfigError> {
+            crate::Resend::try_default().map(Self::from_async)
+        }
+
+        /// Returns the async [`crate::Resend`] client this client wraps.
+        #[inline]
+        pub fn as_async(&self) -> &crate::Resend {
+            &self.inner
+        }
+
+        /// Returns the reference to the used `User-Agent` header value.
+        #[inline]
+        #[must_use]
+        pub fn user_agent(&self) -> &str {
+            self.inner.user_agent()
+        }
+
+        /// Returns the `API key` used for the next request.
+        #[inline]
+        #[must_use]
+        pub fn api_key(&self) -> String {
+            self.inner.api_key()
+        }
+
+        /// Returns the reference to the used `base URL`.
+        #[inline]
+        #[must_use]
+        pub fn base_url(&self) -> &str {
+            self.inner.base_url()
+        }
+    }
+
+    impl Default for Resend {
+        /// Creates a new blocking [`Resend`] client from the `RESEND_API_KEY` environment
+        /// variable.
+        ///
+        /// ### Panics
+        ///
+        /// See [`crate::Resend::default`].
+        fn default() -> Self {
+            Self::from_async(crate::Resend::default())
+        }
+    }
+
+    impl fmt::Debug for Resend {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&self.inner, f)
+        }
+    }
+}
+
+pub mod services {
+    //! Blocking `Resend` API services.
+    //!
+    //! Every method runs the method of the same name of the matching [`crate::services`] type to
+    //! completion. The methods are generated from the async ones by `service_impl!`, so every
+    //! async method has a blocking version with the same signature.
+
+    use std::sync::Arc;
+
+    use tokio::runtime::Runtime;
+
+    macro_rules! blocking_svc {
+        ($($svc:ident),* $(,)?) => {
+            $(
+                #[doc = concat!("Blocking version of [`", stringify!($svc), "`](crate::services::", stringify!($svc), ").")]
+                #[derive(Clone, Debug)]
+                pub struct $svc(pub(crate) crate::services::$svc, pub(crate) Arc<Runtime>);
+
+                impl $svc {
+                    #[doc = concat!("Blocking version of [`", stringify!($svc), "::with_options`](crate::services::", stringify!($svc), "::with_options).")]
+                    #[must_use]
+                    pub fn with_options(&self, options: crate::request_opts::RequestOptions) -> Self {
+                        Self(self.0.with_options(options), Arc::clone(&self.1))
+                    }
+                }
+            )*
+        };
+    }
+
+    blocking_svc!(
+        ApiKeysSvc,
+        AutomationsSvc,
+        BatchSvc,
+        BroadcastsSvc,
+        ContactsSvc,
+        DomainsSvc,
+        EmailsSvc,
+        EventsSvc,
+        LogsSvc,
+        OAuthSvc,
+        ReceivingSvc,
+        SegmentsSvc,
+        SuppressionsSvc,
+        TemplateSvc,
+        TopicsSvc,
+        WebhookSvc,
+    );
+}
+
+mod paginator {
+    use std::{fmt, sync::Arc};
+
+    use futures_util::StreamExt;
+    use serde::de::DeserializeOwned;
+    use tokio::runtime::Runtime;
+
+    use crate::{
+        Result,
+        list_opts::{Cursor, ListItem},
+    };
+
+    /// Blocking version of [`list_opts::Paginator`](crate::list_opts::Paginator), fetching the
+    /// next page whenever the items of the previous one are consumed.
+    pub struct Paginator<I> {
+        paginator: crate::list_opts::Paginator<I>,
+        runtime: Arc<Runtime>,
+    }
+
+    impl<I> Paginator<I> {
+        pub(crate) const fn new(
+            runtime: Arc<Runtime>,
+            paginator: crate::list_opts::Paginator<I>,
+        ) -> Self {
+            Self { paginator, runtime }
+        }
+
+        /// See [`list_opts::Paginator::cursor`](crate::list_opts::Paginator::cursor).
+        pub fn cursor(&self) -> Option<Cursor> {
+            self.paginator.cursor()
+        }
+    }
+
+    impl<I> fmt::Debug for Paginator<I> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&self.paginator, f)
+        }
+    }
+
+    impl<I> Iterator for Paginator<I>
+    where
+        I: ListItem + DeserializeOwned + Send + Unpin + 'static,
+    {
+        type Item = Result<I>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.runtime.block_on(self.paginator.next())
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    #[test]
+    fn send_with_retry_opts() {
+        use std::cell::Cell;
+
+        use super::send_with_retry_opts;
+        use crate::{Error, rate_limit::RetryOptions};
+
+        let calls = Cell::new(0);
+        let f = || {
+            calls.set(calls.get() + 1);
+            Result::<(), Error>::Err(Error::RateLimit {
+                ratelimit_limit: Some(10),
+                ratelimit_remaining: Some(0),
+                ratelimit_reset: None,
+                context: Box::default(),
+            })
+        };
+        let opts = RetryOptions {
+            duration_ms: 1,
+            jitter_range_ms: 0..1,
+            max_retries: 2,
+        };
+
+        assert!(send_with_retry_opts(f, &opts).is_err());
+        assert_eq!(calls.get(), 3);
+
+        calls.set(0);
+        let res = send_with_retry_opts(
+            || {
+                calls.set(calls.get() + 1);
+                Ok(())
+            },
+            &opts,
+        );
+        assert!(res.is_ok());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "testing")]
+    fn side_by_side() -> crate::test::DebugResult<()> {
+        use crate::{list_opts::ListOptions, testing::MockServer, types::CreateEmailBaseOptions};
+
+        let server = MockServer::start();
+        let email = CreateEmailBaseOptions::new("acme@a.dev", ["b@b.dev"], "Hi").with_text("Hi");
+
+        let resend = crate::blocking::Resend::with_config(server.config().build());
+        let id = resend.emails.send(email.clone())?.id;
+        assert_eq!(resend.emails.get(&id)?.subject, "Hi");
+        let _second = resend.emails.send(email)?;
+        let all = resend
+            .emails
+            .list_all(ListOptions::default().with_limit(1))
+            .collect::<crate::Result<Vec<_>>>()?;
+        assert_eq!(all.len(), 2);
+
+        // The async client keeps working in the same build.
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let emails = runtime.block_on(server.client().emails.list(ListOptions::default()))?;
+        assert_eq!(emails.len(), 2);
+
+        Ok(())
+    }
+}
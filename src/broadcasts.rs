@@ -1,9 +1,14 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 use types::{ListRecipientsOptions, UpdateBroadcastOptions, UpdateBroadcastResponse};
 
-use crate::{Config, Result, list_opts::ListResponse};
+use crate::{
+    Config, Result,
+    list_opts::{ListResponse, Paginator},
+    meta::WithMeta,
+};
 use crate::{
     list_opts::ListOptions,
     types::{
@@ -17,18 +22,20 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct BroadcastsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl BroadcastsSvc {
     /// Create a new broadcast to send to your audience.
     ///
     /// <https://resend.com/docs/api-reference/broadcasts/create-broadcast>
-    #[maybe_async::maybe_async]
     pub async fn create(
         &self,
         broadcast: CreateBroadcastOptions,
     ) -> Result<CreateBroadcastResponse> {
         let request = self.0.build(Method::POST, "/broadcasts");
-        let response = self.0.send(request.json(&broadcast)).await?;
-        let content = response.json::<CreateBroadcastResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateBroadcastResponse>(request.json(&broadcast))
+            .await?;
 
         Ok(content)
     }
@@ -36,15 +43,21 @@ impl BroadcastsSvc {
     /// Start sending broadcasts to your audience through the Resend API.
     ///
     /// <https://resend.com/docs/api-reference/broadcasts/send-broadcast>
-    #[maybe_async::maybe_async]
     pub async fn send(&self, broadcast: SendBroadcastOptions) -> Result<SendBroadcastResponse> {
+        Ok(self.send_with_meta(broadcast).await?.data)
+    }
+
+    /// The same as [`BroadcastsSvc::send`] but also returns the [`ResponseMeta`] of the response.
+    ///
+    /// [`ResponseMeta`]: crate::meta::ResponseMeta
+    pub async fn send_with_meta(
+        &self,
+        broadcast: SendBroadcastOptions,
+    ) -> Result<WithMeta<SendBroadcastResponse>> {
         let path = format!("/broadcasts/{}/send", broadcast.broadcast_id);
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request.json(&broadcast)).await?;
-        let content = response.json::<SendBroadcastResponse>().await?;
-
-        Ok(content)
+        self.0.send_json_with_meta(request.json(&broadcast)).await
     }
 
     /// Retrieve a list of broadcasts.
@@ -52,36 +65,37 @@ impl BroadcastsSvc {
     /// - Default limit: no limit (return everything)
     ///
     /// <https://resend.com/docs/api-reference/broadcasts/list-broadcasts>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Broadcast>> {
         let request = self.0.build(Method::GET, "/broadcasts").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Broadcast>>().await?;
+        let content = self.0.send_json::<ListResponse<Broadcast>>(request).await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Broadcast> {
+        Paginator::new(Arc::clone(&self.0), "/broadcasts".to_owned(), &list_opts)
+    }
+
     /// Retrieve a single broadcast.
     ///
     /// <https://resend.com/docs/api-reference/broadcasts/get-broadcast>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, broadcast_id: &str) -> Result<Broadcast> {
         let path = format!("/broadcasts/{broadcast_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Broadcast>().await?;
+        let content = self.0.send_json::<Broadcast>(request).await?;
 
         Ok(content)
     }
 
-    #[maybe_async::maybe_async]
     pub async fn cancel(&self, broadcast_id: &str) -> Result<CancelBroadcastResponse> {
         let path = format!("/broadcasts/{broadcast_id}/cancel");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<CancelBroadcastResponse>().await?;
+        let content = self.0.send_json::<CancelBroadcastResponse>(request).await?;
 
         Ok(content)
     }
@@ -89,13 +103,11 @@ impl BroadcastsSvc {
     /// Remove an existing broadcast.
     ///
     /// <https://resend.com/docs/api-reference/broadcasts/delete-broadcast>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, broadcast_id: &str) -> Result<bool> {
         let path = format!("/broadcasts/{broadcast_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<RemoveBroadcastResponse>().await?;
+        let content = self.0.send_json::<RemoveBroadcastResponse>(request).await?;
 
         Ok(content.deleted)
     }
@@ -103,7 +115,6 @@ impl BroadcastsSvc {
     /// Retrieve the links clicked in a broadcast, ranked by total clicks.
     ///
     /// <https://resend.com/docs/api-reference/broadcasts/list-broadcast-clicked-links>
-    #[maybe_async::maybe_async]
     pub async fn clicked_links<T>(
         &self,
         broadcast_id: &str,
@@ -112,16 +123,28 @@ impl BroadcastsSvc {
         let path = format!("/broadcasts/{broadcast_id}/clicked-links");
 
         let request = self.0.build(Method::GET, &path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response
-            .json::<ListResponse<BroadcastClickedLink>>()
+        let content = self
+            .0
+            .send_json::<ListResponse<BroadcastClickedLink>>(request)
             .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::clicked_links`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn clicked_links_all<T>(
+        &self,
+        broadcast_id: &str,
+        list_opts: ListOptions<T>,
+    ) -> Paginator<BroadcastClickedLink> {
+        let path = format!("/broadcasts/{broadcast_id}/clicked-links");
+
+        Paginator::new(Arc::clone(&self.0), path, &list_opts)
+    }
+
     /// Update a broadcast to send to your audience.
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         broadcast_id: &str,
@@ -130,8 +153,10 @@ impl BroadcastsSvc {
         let path = format!("/broadcasts/{broadcast_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateBroadcastResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateBroadcastResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -142,7 +167,6 @@ impl BroadcastsSvc {
     /// - Default limit: 20
     ///
     /// <https://resend.com/docs/api-reference/broadcasts/list-broadcast-recipients>
-    #[maybe_async::maybe_async]
     pub async fn recipients<T>(
         &self,
         broadcast_id: &str,
@@ -151,11 +175,26 @@ impl BroadcastsSvc {
         let path = format!("/broadcasts/{broadcast_id}/recipients");
 
         let request = self.0.build(Method::GET, &path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<BroadcastRecipient>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<BroadcastRecipient>>(request)
+            .await?;
 
         Ok(content)
     }
+
+    /// Retrieves the items of every page of [`Self::recipients`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn recipients_all<T>(
+        &self,
+        broadcast_id: &str,
+        list_opts: ListRecipientsOptions<T>,
+    ) -> Paginator<BroadcastRecipient> {
+        let path = format!("/broadcasts/{broadcast_id}/recipients");
+
+        Paginator::new(Arc::clone(&self.0), path, &list_opts)
+    }
 }
 
 #[allow(unreachable_pub)]
@@ -164,7 +203,7 @@ pub mod types {
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        list_opts::{ListAfter, ListBefore, ListOptions, TimeNotSpecified},
+        list_opts::{Cursor, ListAfter, ListBefore, ListCursor, ListOptions, TimeNotSpecified},
         types::{ContactId, SegmentId},
     };
 
@@ -512,6 +551,21 @@ pub mod types {
                 pagination: self.pagination.list_after(id),
             }
         }
+
+        /// Continues the listing at `cursor`, see [`ListOptions::resume`]. The event type,
+        /// email and bounce type are the ones set here, not the ones of the cursor.
+        pub fn resume(self, mut cursor: Cursor) -> ListRecipientsOptions<ListCursor> {
+            for key in ["type", "email", "bounce_type"] {
+                let _old = cursor.filters.remove(key);
+            }
+
+            ListRecipientsOptions {
+                r#type: self.r#type,
+                email: self.email,
+                bounce_type: self.bounce_type,
+                pagination: self.pagination.resume(cursor),
+            }
+        }
     }
 
     impl<T> ListRecipientsOptions<T> {
@@ -593,7 +647,6 @@ pub mod types {
 #[cfg(test)]
 #[allow(clippy::needless_return, clippy::indexing_slicing)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -610,7 +663,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     #[ignore = "Can no longer send broadcasts from the resend.dev domain"]
     async fn create_send_broadcast() -> DebugResult<()> {
         let resend = &*CLIENT;
@@ -656,7 +708,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     #[ignore = "Can no longer send broadcasts from the resend.dev domain"]
     async fn list_get_broadcast() -> DebugResult<()> {
         let resend = &*CLIENT;
@@ -693,7 +744,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     #[ignore = "Can no longer send broadcasts from the resend.dev domain"]
     async fn update_broadcast() -> DebugResult<()> {
         let resend = &*CLIENT;
@@ -877,7 +927,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     #[ignore = "requires RESEND_API_KEY and network access"]
     async fn recipients_not_found() -> DebugResult<()> {
         let resend = &*CLIENT;
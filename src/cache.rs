@@ -0,0 +1,460 @@
+//! Read-through cache for slow-changing resources.
+//!
+//! A [`Cache`] set with [`ConfigBuilder::cache`] answers `GET` requests to the resources it has a
+//! TTL for with the last successful response, as long as it is fresh. Cached responses don't
+//! reach the transport and thus don't count towards the rate limit.
+//!
+//! Any other request to a resource sent through a client sharing the cache (e.g. `update`,
+//! `delete` or `publish`) invalidates all its cached responses, both before it is sent and once
+//! it completed, whether it succeeded or not. Responses to `GET` requests that were in flight
+//! meanwhile are not cached, as they may predate the change. Changes made elsewhere, e.g. in the
+//! dashboard, are only seen once the TTL expires, or after an explicit invalidation with
+//! [`Cache::invalidate`] or [`Cache::invalidate_event`] from a webhook handler.
+//!
+//! Responses are cached by API key and url (including the base url and the query), so a cache can
+//! be shared by clients using API keys of different teams or different base urls. The API keys are
+//! not kept around, only their HMAC with a secret random key of the cache.
+//!
+//! Cached responses never reach the [interceptors](crate::interceptor): neither
+//! [`Interceptor::on_request`] nor [`Interceptor::on_response`] is called for them.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use std::{sync::Arc, time::Duration};
+//!
+//! use resend_rs::cache::{Cache, Resource};
+//! use resend_rs::{ConfigBuilder, Resend};
+//!
+//! let cache = Cache::new()
+//!     .with_ttl(Resource::Templates, Duration::from_secs(300))
+//!     .with_ttl(Resource::Domains, Duration::from_secs(60))
+//!     .with_ttl(Resource::Topics, Duration::from_secs(60));
+//! let cache = Arc::new(cache);
+//!
+//! let config = ConfigBuilder::new("re_...")
+//!     .cache(Arc::clone(&cache))
+//!     .build();
+//! let resend = Resend::with_config(config);
+//!
+//! // e.g. in a webhook handler
+//! // cache.invalidate_event(&event);
+//! ```
+//!
+//! [`ConfigBuilder::cache`]: crate::ConfigBuilder::cache
+//! [`Interceptor::on_request`]: crate::interceptor::Interceptor::on_request
+//! [`Interceptor::on_response`]: crate::interceptor::Interceptor::on_response
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use hmac_sha256::HMAC;
+use rand::RngExt;
+use reqwest::{Method, StatusCode, Url, header::HeaderMap};
+
+use reqwest::Response;
+
+use crate::{Result, events::Event};
+
+/// A resource whose responses can be cached.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resource {
+    /// `/domains`, see [`DomainsSvc`](crate::services::DomainsSvc).
+    Domains,
+    /// `/segments`, see [`SegmentsSvc`](crate::services::SegmentsSvc).
+    Segments,
+    /// `/templates`, see [`TemplateSvc`](crate::services::TemplateSvc).
+    Templates,
+    /// `/topics`, see [`TopicsSvc`](crate::services::TopicsSvc).
+    Topics,
+    /// `/webhooks`, see [`WebhookSvc`](crate::services::WebhookSvc).
+    Webhooks,
+}
+
+impl Resource {
+    /// Returns the resource of `endpoint`, e.g. [`Resource::Templates`] for
+    /// `/templates/welcome`.
+    fn of(endpoint: &str) -> Option<Self> {
+        let segment = endpoint.trim_start_matches('/').split(['/', '?']).next()?;
+
+        match segment {
+            "domains" => Some(Self::Domains),
+            "segments" => Some(Self::Segments),
+            "templates" => Some(Self::Templates),
+            "topics" => Some(Self::Topics),
+            "webhooks" => Some(Self::Webhooks),
+            _ => None,
+        }
+    }
+}
+
+/// A cached response.
+#[derive(Debug)]
+struct Entry {
+    resource: Resource,
+    expires_at: Instant,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl Entry {
+    fn response(&self) -> Response {
+        let mut response = http::Response::new(self.body.clone());
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+
+        response.into()
+    }
+}
+
+/// The key of a cached response: the HMAC of the credential and the url.
+type Key = ([u8; 32], String);
+
+/// The cached responses and the generation of every resource, bumped by every invalidation.
+#[derive(Debug, Default)]
+struct State {
+    entries: HashMap<Key, Entry>,
+    generations: HashMap<Resource, u64>,
+}
+
+impl State {
+    fn generation(&self, resource: Resource) -> u64 {
+        self.generations.get(&resource).copied().unwrap_or_default()
+    }
+
+    fn invalidate(&mut self, resource: Resource) {
+        let generation = self.generations.entry(resource).or_default();
+        *generation = generation.wrapping_add(1);
+        self.entries.retain(|_, entry| entry.resource != resource);
+    }
+}
+
+/// A request to a resource of a [`Cache`], from [`Cache::begin`] to [`Cache::finish`].
+#[derive(Debug)]
+pub(crate) struct Pending {
+    resource: Resource,
+    /// The key and the generation of the resource at the start of a `GET` request, `None` for
+    /// requests that may change the resource.
+    read: Option<(Key, u64)>,
+}
+
+/// Read-through cache shared by clients, see the [module level documentation](self).
+///
+/// Note that [`Cache::default()`] caches nothing until a TTL is set with [`Cache::with_ttl`].
+pub struct Cache {
+    ttls: HashMap<Resource, Duration>,
+    /// The key of the HMAC of the credentials.
+    secret: [u8; 32],
+    state: Mutex<State>,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self {
+            ttls: HashMap::new(),
+            secret: rand::rng().random(),
+            state: Mutex::default(),
+        }
+    }
+}
+
+impl fmt::Debug for Cache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("ttls", &self.ttls)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Cache {
+    /// Creates a new empty [`Cache`], without any TTL.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caches the responses of `resource` for `ttl`.
+    #[must_use]
+    pub fn with_ttl(mut self, resource: Resource, ttl: Duration) -> Self {
+        let _old = self.ttls.insert(resource, ttl);
+        self
+    }
+
+    /// Returns the TTL of `resource`, `None` if its responses are not cached.
+    #[must_use]
+    pub fn ttl(&self, resource: Resource) -> Option<Duration> {
+        self.ttls.get(&resource).copied()
+    }
+
+    /// Removes the cached responses of `resource`.
+    pub fn invalidate(&self, resource: Resource) {
+        self.lock().invalidate(resource);
+    }
+
+    /// Removes all cached responses.
+    pub fn invalidate_all(&self) {
+        let mut state = self.lock();
+        for &resource in self.ttls.keys() {
+            state.invalidate(resource);
+        }
+        state.entries.clear();
+    }
+
+    /// Removes the cached responses that are stale after the webhook `event`, e.g. the ones of
+    /// [`Resource::Domains`] after a `domain.updated` event.
+    pub fn invalidate_event(&self, event: &Event) {
+        match event {
+            Event::DomainEvent(_) => self.invalidate(Resource::Domains),
+            Event::EmailEvent(_) | Event::ContactEvent(_) | Event::SuppressionEvent(_) => {}
+        }
+    }
+
+    /// Returns the amount of cached responses, including expired ones.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns `true` if no response is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Starts the request `method` of `url` (whose path relative to the base url is `endpoint`)
+    /// made with the `credential` (the `Authorization` header), `None` if its resource is not
+    /// cached.
+    ///
+    /// Requests that may change the resource invalidate it right away.
+    pub(crate) fn begin(
+        &self,
+        method: &Method,
+        credential: &[u8],
+        url: &Url,
+        endpoint: &str,
+    ) -> Option<Pending> {
+        let resource = Resource::of(endpoint)?;
+        if method != Method::GET {
+            self.invalidate(resource);
+            return Some(Pending {
+                resource,
+                read: None,
+            });
+        }
+        let _ttl = self.ttl(resource)?;
+
+        // Don't keep the API key around.
+        let key = (HMAC::mac(credential, self.secret), url.to_string());
+        let generation = self.lock().generation(resource);
+
+        Some(Pending {
+            resource,
+            read: Some((key, generation)),
+        })
+    }
+
+    /// Returns a fresh cached response to the `GET` request `pending`.
+    pub(crate) fn get(&self, pending: &Pending) -> Option<Response> {
+        let (key, _) = pending.read.as_ref()?;
+        let state = self.lock();
+        let response = state
+            .entries
+            .get(key)
+            .filter(|entry| entry.expires_at > Instant::now())
+            .map(Entry::response);
+        drop(state);
+
+        response
+    }
+
+    /// Completes the request `pending`: caches its `response` if it is a successful `GET` and
+    /// the resource was not invalidated meanwhile, or invalidates the resource again if the
+    /// request may have changed it.
+    ///
+    /// Returns `response`, which has to be rebuilt if its body was read.
+    pub(crate) async fn finish(
+        &self,
+        pending: Pending,
+        response: Result<Response>,
+    ) -> Result<Response> {
+        let Some((key, generation)) = pending.read else {
+            self.invalidate(pending.resource);
+            return response;
+        };
+        let response = response?;
+        let Some(ttl) = self.ttl(pending.resource) else {
+            return Ok(response);
+        };
+        if !response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+        let entry = Entry {
+            resource: pending.resource,
+            expires_at: Instant::now() + ttl,
+            status,
+            headers,
+            body,
+        };
+        let response = entry.response();
+
+        let mut state = self.lock();
+        if state.generation(pending.resource) == generation {
+            let now = Instant::now();
+            state.entries.retain(|_, entry| entry.expires_at > now);
+            let _old = state.entries.insert(key, entry);
+        }
+        drop(state);
+
+        Ok(response)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::Resource;
+    use crate::test::{ScriptedTransport, json_response};
+
+    #[test]
+    fn resource() {
+        assert_eq!(
+            Resource::of("/templates/welcome"),
+            Some(Resource::Templates)
+        );
+        assert_eq!(Resource::of("/domains?limit=10"), Some(Resource::Domains));
+        assert_eq!(Resource::of("/topics"), Some(Resource::Topics));
+        assert_eq!(Resource::of("/emails/batch"), None);
+    }
+
+    /// Responds to `GET /topics/{id}` with a topic and to `DELETE` with a deleted topic, or a
+    /// `404` for the topic `missing`.
+    fn topics() -> ScriptedTransport {
+        ScriptedTransport::new(|_, request| {
+            if request.url().path().ends_with("/missing") {
+                let body = r#"{"statusCode":404,"message":"","name":"not_found"}"#;
+                return Ok(json_response(404, body));
+            }
+            let body = if request.method() == reqwest::Method::DELETE {
+                r#"{"object":"topic","id":"t","deleted":true}"#
+            } else {
+                r#"{
+                    "object": "topic",
+                    "id": "t",
+                    "name": "Newsletter",
+                    "description": null,
+                    "default_subscription": "opt_in",
+                    "visibility": "public",
+                    "created_at": "2023-04-08T00:11:13.110779+00:00"
+                }"#
+            };
+
+            Ok(json_response(200, body))
+        })
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn read_through() {
+        use std::{sync::Arc, time::Duration};
+
+        use super::Cache;
+        use crate::{ConfigBuilder, Resend};
+
+        let transport = topics();
+        let cache = Arc::new(Cache::new().with_ttl(Resource::Topics, Duration::from_millis(100)));
+        let config = ConfigBuilder::new("re_test")
+            .transport(transport.clone())
+            .cache(Arc::clone(&cache))
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        for _ in 0..3 {
+            let topic = resend.topics.get("t").await.unwrap();
+            assert_eq!(topic.name, "Newsletter");
+        }
+        assert_eq!(transport.calls(), 1);
+        assert_eq!(cache.len(), 1);
+
+        // Mutations invalidate the resource
+        let _deleted = resend.topics.delete("t").await.unwrap();
+        assert!(cache.is_empty());
+        let _topic = resend.topics.get("t").await.unwrap();
+        assert_eq!(transport.calls(), 3);
+
+        // Expired responses are fetched again
+        tokio::time::sleep(Duration::from_millis(110)).await;
+        let _topic = resend.topics.get("t").await.unwrap();
+        assert_eq!(transport.calls(), 4);
+
+        // Resources without a TTL are not cached
+        cache.invalidate(Resource::Topics);
+        assert!(cache.is_empty());
+        let _domains = resend
+            .domains
+            .list(crate::list_opts::ListOptions::default())
+            .await;
+        assert!(cache.is_empty());
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn invalidation() {
+        use std::{sync::Arc, time::Duration};
+
+        use super::Cache;
+        use crate::{ConfigBuilder, Resend};
+
+        let transport = topics();
+        let cache = Arc::new(Cache::new().with_ttl(Resource::Topics, Duration::from_mins(1)));
+        let client = |api_key, base_url: &str| {
+            let config = ConfigBuilder::new(api_key)
+                .base_url(base_url.parse().unwrap())
+                .transport(transport.clone())
+                .cache(Arc::clone(&cache))
+                .without_rate_limit()
+                .build();
+            Resend::with_config(config)
+        };
+        let first = client("re_first", "https://api.resend.com");
+        let second = client("re_second", "https://api.resend.com");
+        let proxied = client("re_first", "http://proxy.local");
+
+        // Responses are cached per API key and base url
+        let _topic = first.topics.get("t").await.unwrap();
+        let _topic = second.topics.get("t").await.unwrap();
+        let _topic = second.topics.get("t").await.unwrap();
+        let _topic = proxied.topics.get("t").await.unwrap();
+        let _topic = proxied.topics.get("t").await.unwrap();
+        assert_eq!(transport.calls(), 3);
+        assert_eq!(cache.len(), 3);
+
+        // Failed mutations invalidate the resource as well
+        let res = first.topics.delete("missing").await;
+        assert!(res.is_err());
+        assert!(cache.is_empty());
+
+        // Responses of requests that were in flight during an invalidation are not cached
+        let url = "https://api.resend.com/topics/t".parse().unwrap();
+        let pending = cache
+            .begin(&reqwest::Method::GET, b"Bearer re_first", &url, "/topics/t")
+            .unwrap();
+        cache.invalidate(Resource::Topics);
+        let response = json_response(200, "{}");
+        let _response = cache.finish(pending, Ok(response)).await.unwrap();
+        assert!(cache.is_empty());
+    }
+}
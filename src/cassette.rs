@@ -0,0 +1,529 @@
+//! Record/replay of API interactions.
+//!
+//! In record mode ([`ConfigBuilder::record`]) every request and response going through
+//! [`Config::send`] is recorded and written to a cassette file once the client is dropped (or
+//! with [`Recorder::save`]). In replay mode ([`ConfigBuilder::replay`]) no
+//! requests are made and the recorded responses are served instead, matching on the method,
+//! path, query and body of each request.
+//!
+//! This makes it possible to capture the real Resend behaviour once and run integration tests
+//! without credentials or network access afterwards.
+//!
+//! Cassettes never contain the `Authorization` header, and the API key sent with each request
+//! (which may change with a [`CredentialProvider`]) as well as anything else that looks like an
+//! API key (`re_...`) is scrubbed from the recorded bodies.
+//!
+//! ### Example
+//!
+//! ```rust,no_run
+//! use resend_rs::{ConfigBuilder, Resend};
+//!
+//! // Record once
+//! let config = ConfigBuilder::new("re_...")
+//!     .record("tests/cassettes/emails.json")
+//!     .build();
+//!
+//! // Replay forever after
+//! let config = ConfigBuilder::new("re_dummy")
+//!     .replay("tests/cassettes/emails.json")
+//!     .build();
+//! let resend = Resend::with_config(config);
+//! ```
+//!
+//! [`Config::send`]: crate::Config::send
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use reqwest::{Request, Response, header::AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Error, Result, transport::Transport};
+
+#[cfg(doc)]
+use crate::{ConfigBuilder, credentials::CredentialProvider};
+
+/// A recorded list of request/response pairs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Reads a [`Cassette`] from the JSON file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Error::other(format!("failed to read {}: {e}", path.display())))?;
+
+        serde_json::from_str(&content).map_err(|e| Error::parse(content, e))
+    }
+
+    /// Writes the [`Cassette`] as JSON to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::other(format!("failed to serialize cassette: {e}")))?;
+
+        std::fs::write(path, content)
+            .map_err(|e| Error::other(format!("failed to write {}: {e}", path.display())))
+    }
+}
+
+/// A single recorded request and its response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// The parts of a request used for matching.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// Sorted query parameters.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<(String, String)>,
+    /// The JSON body, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+impl RecordedRequest {
+    fn new(request: &Request, api_key: &str) -> Self {
+        let url = request.url();
+
+        let mut query = url
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect::<Vec<_>>();
+        query.sort();
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .filter(|bytes| !bytes.is_empty())
+            .map(|bytes| {
+                let text = scrub(&String::from_utf8_lossy(bytes), api_key);
+                serde_json::from_str(&text).unwrap_or(Value::String(text))
+            });
+
+        Self {
+            method: request.method().to_string(),
+            path: url.path().to_owned(),
+            query,
+            body,
+        }
+    }
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> Result<Response> {
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder
+            .body(self.body.clone())
+            .map_err(|e| Error::other(format!("invalid recorded response: {e}")))?;
+
+        Ok(Response::from(response))
+    }
+}
+
+/// [`Transport`] that records every interaction to a cassette file.
+///
+/// The interactions are kept in memory and written with [`Recorder::save`], or once the recorder
+/// and all its clones are dropped (e.g. with the last clone of the client). Failing to write the
+/// cassette never fails a request, as it was sent already.
+///
+/// Usually constructed through [`ConfigBuilder::record`].
+#[derive(Clone)]
+pub struct Recorder {
+    inner: Arc<dyn Transport>,
+    recording: Arc<Recording>,
+}
+
+impl Recorder {
+    /// Creates a new [`Recorder`] executing requests with `inner` and writing them to `path`.
+    ///
+    /// Any occurrence of the API key of a request is scrubbed from the cassette.
+    pub fn new(inner: Arc<dyn Transport>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            recording: Arc::new(Recording {
+                path: path.into(),
+                cassette: Mutex::new(Cassette::default()),
+                unsaved: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Writes the interactions recorded so far to the cassette file, overwriting it.
+    ///
+    /// # Blocking
+    ///
+    /// The file is written synchronously.
+    pub fn save(&self) -> Result<()> {
+        self.recording.save()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Transport for Recorder {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        // The key actually sent, which may differ from request to request.
+        let api_key = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .unwrap_or_default()
+            .to_owned();
+        let recorded_request = RecordedRequest::new(&request, &api_key);
+
+        let response = self.inner.execute(request).await?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect::<Vec<_>>();
+        let body = response.text().await?;
+
+        let recorded_response = RecordedResponse {
+            status,
+            headers,
+            body: scrub(&body, &api_key),
+        };
+        let replayed = RecordedResponse {
+            body,
+            ..recorded_response.clone()
+        }
+        .to_response()?;
+
+        self.recording.push(Interaction {
+            request: recorded_request,
+            response: recorded_response,
+        });
+
+        Ok(replayed)
+    }
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("inner", &self.inner)
+            .field("path", &self.recording.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The interactions recorded by a [`Recorder`] and its clones, written when dropped.
+struct Recording {
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+    /// Whether interactions were recorded since the cassette was last written.
+    unsaved: AtomicBool,
+}
+
+impl Recording {
+    fn push(&self, interaction: Interaction) {
+        self.lock().interactions.push(interaction);
+        self.unsaved.store(true, Ordering::Release);
+    }
+
+    fn save(&self) -> Result<()> {
+        // Not holding the lock while writing, so that requests are not blocked meanwhile.
+        self.unsaved.store(false, Ordering::Release);
+        let cassette = self.lock().clone();
+
+        cassette
+            .save(&self.path)
+            .inspect_err(|_| self.unsaved.store(true, Ordering::Release))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Cassette> {
+        self.cassette.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        if !self.unsaved.load(Ordering::Acquire) {
+            return;
+        }
+
+        let result = self.save();
+
+        #[cfg(feature = "tracing")]
+        if let Err(ref error) = result {
+            tracing::error!(%error, path = %self.path.display(), "failed to save cassette");
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        let _result = result;
+    }
+}
+
+/// [`Transport`] that serves the responses of a [`Cassette`] instead of making requests.
+///
+/// Requests are matched on their method, path, query and body. Identical requests are served in
+/// the order they were recorded in. Unmatched requests fail with [`Error::Other`].
+///
+/// Usually constructed through [`ConfigBuilder::replay`].
+#[derive(Debug)]
+pub struct Player {
+    /// Recorded interactions and whether they have already been served.
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+
+impl Player {
+    /// Creates a new [`Player`] serving the interactions of `cassette`.
+    pub fn new(cassette: Cassette) -> Self {
+        let interactions = cassette
+            .interactions
+            .into_iter()
+            .map(|interaction| (interaction, false))
+            .collect();
+
+        Self {
+            interactions: Mutex::new(interactions),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Transport for Player {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let recorded_request = RecordedRequest::new(&request, "");
+
+        let mut interactions = self
+            .interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let response = interactions
+            .iter_mut()
+            .find(|(interaction, served)| !served && interaction.request == recorded_request)
+            .map(|(interaction, served)| {
+                *served = true;
+                interaction.response.to_response()
+            });
+        drop(interactions);
+
+        response.unwrap_or_else(|| {
+            Err(Error::other(format!(
+                "no recorded interaction matches {} {}",
+                recorded_request.method, recorded_request.path
+            )))
+        })
+    }
+}
+
+/// Replaces `api_key` and anything that looks like an API key (`re_...`) in `text`.
+fn scrub(text: &str, api_key: &str) -> String {
+    let text = if api_key.is_empty() {
+        text.to_owned()
+    } else {
+        text.replace(api_key, "re_REDACTED")
+    };
+
+    let mut scrubbed = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+
+    while let Some(index) = rest.find("re_") {
+        let (before, after) = rest.split_at(index);
+        scrubbed.push_str(before);
+
+        let starts_word = !before
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+        let key_len = after
+            .chars()
+            .skip(3)
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .count();
+
+        if starts_word && key_len >= 8 {
+            scrubbed.push_str("re_REDACTED");
+            rest = after.get(3 + key_len..).unwrap_or_default();
+        } else {
+            scrubbed.push_str("re_");
+            rest = after.get(3..).unwrap_or_default();
+        }
+    }
+    scrubbed.push_str(rest);
+
+    scrubbed
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::scrub;
+
+    #[test]
+    fn scrub_api_keys() {
+        let text =
+            r#"{"id":"1","token":"re_c1tpEyD8_NKFusih9vKVQknRAQfmFcWCv","name":"are_you_ok"}"#;
+        assert_eq!(
+            scrub(text, ""),
+            r#"{"id":"1","token":"re_REDACTED","name":"are_you_ok"}"#
+        );
+        assert_eq!(scrub("Bearer re_123", "re_123"), "Bearer re_REDACTED");
+        assert_eq!(scrub("re_short", ""), "re_short");
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    #[cfg(feature = "testing")]
+    async fn record_and_replay() -> crate::test::DebugResult<()> {
+        use crate::{
+            ConfigBuilder, Error, Resend, cassette::Cassette, list_opts::ListOptions,
+            testing::MockServer, types::CreateEmailBaseOptions,
+        };
+
+        let path =
+            std::env::temp_dir().join(format!("resend-cassette-{}.json", std::process::id()));
+        let email = CreateEmailBaseOptions::new("acme@a.dev", ["b@b.dev"], "Hi").with_text("Hi");
+
+        // Record
+        let server = MockServer::start();
+        let config = server.config().record(&path).build();
+        let resend = Resend::with_config(config);
+        let id = resend.emails.send(email.clone()).await?.id;
+        let _list = resend.emails.list(ListOptions::default()).await?;
+        // The cassette is written once the client is dropped
+        drop(resend);
+        drop(server);
+
+        let cassette = Cassette::load(&path)?;
+        assert_eq!(cassette.interactions.len(), 2);
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("re_mock_server"));
+
+        // Replay
+        let config = ConfigBuilder::new("re_other").replay(&path).build();
+        let resend = Resend::with_config(config);
+        assert_eq!(resend.emails.send(email.clone()).await?.id, id);
+        let list = resend.emails.list(ListOptions::default()).await?;
+        assert_eq!(list.len(), 1);
+
+        // Exhausted
+        let res = resend.emails.send(email).await;
+        assert!(matches!(res, Err(Error::Other { .. })));
+
+        std::fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn scrub_rotated_keys() {
+        use std::sync::Arc;
+
+        use crate::{
+            ConfigBuilder, Resend,
+            cassette::{Cassette, Recorder},
+            credentials::ApiKeyCell,
+            test::{ScriptedTransport, json_response},
+            types::ShareEmailOptions,
+        };
+
+        // Echoes the API key of the request
+        let transport = ScriptedTransport::new(|_, request| {
+            let authorization = request.headers()["authorization"].to_str().unwrap();
+            let api_key = authorization.trim_start_matches("Bearer ");
+            let body = serde_json::json!({ "id": "abc", "url": api_key });
+            Ok(json_response(200, body.to_string()))
+        });
+        let path = std::env::temp_dir().join(format!(
+            "resend-rotated-cassette-{}.json",
+            std::process::id()
+        ));
+        let recorder = Recorder::new(Arc::new(transport), &path);
+        let api_key = Arc::new(ApiKeyCell::new("first-key"));
+        let config = ConfigBuilder::with_credentials(Arc::clone(&api_key))
+            .transport(recorder.clone())
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        let shared = resend
+            .emails
+            .share("abc", ShareEmailOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(shared.url, "first-key");
+        let _old = api_key.set("second-key");
+        let _shared = resend
+            .emails
+            .share("abc", ShareEmailOptions::new())
+            .await
+            .unwrap();
+        recorder.save().unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("first-key"));
+        assert!(!raw.contains("second-key"));
+        let cassette = Cassette::load(&path).unwrap();
+        let urls: Vec<_> = cassette
+            .interactions
+            .iter()
+            .map(|interaction| interaction.response.body.as_str())
+            .collect();
+        assert_eq!(urls, [r#"{"id":"abc","url":"re_REDACTED"}"#; 2]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn failed_save() {
+        use std::sync::Arc;
+
+        use crate::{
+            ConfigBuilder, Resend, cassette::Recorder, test::ScriptedTransport,
+            types::ShareEmailOptions,
+        };
+
+        let transport =
+            ScriptedTransport::respond(200, r#"{"id":"abc","url":"https://resend.com/share/abc"}"#);
+        let path = std::env::temp_dir().join("resend-missing-dir/cassette.json");
+        let recorder = Recorder::new(Arc::new(transport), path);
+        let config = ConfigBuilder::new("re_test")
+            .transport(recorder.clone())
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        // The request succeeds even though the cassette can't be written
+        let shared = resend
+            .emails
+            .share("abc", ShareEmailOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(shared.id.as_ref(), "abc");
+        assert!(recorder.save().is_err());
+    }
+}
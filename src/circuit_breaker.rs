@@ -0,0 +1,456 @@
+//! Circuit breaker failing requests fast while the Resend API is unavailable.
+//!
+//! A [`CircuitBreaker`] set with [`ConfigBuilder::circuit_breaker`] keeps track of the outcome
+//! of recent requests. Once the rate of `5xx` responses, timeouts and connection errors reaches
+//! [`CircuitBreakerPolicy::failure_rate`], the circuit *opens* and every request fails
+//! immediately with [`Error::CircuitOpen`] instead of piling up. After
+//! [`CircuitBreakerPolicy::open_duration`] the circuit is *half-open*: a few probe requests are
+//! let through, if they succeed the circuit closes again, otherwise it reopens.
+//!
+//! `429` responses are handled by the rate limit instead and don't count towards the failure
+//! rate, neither do other `4xx` responses count as failures.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use std::{sync::Arc, time::Duration};
+//!
+//! use resend_rs::circuit_breaker::{CircuitBreaker, CircuitBreakerPolicy, CircuitState};
+//! use resend_rs::{ConfigBuilder, Resend};
+//!
+//! let policy = CircuitBreakerPolicy::default()
+//!     .with_failure_rate(0.25)
+//!     .with_open_duration(Duration::from_secs(10));
+//! let breaker = Arc::new(CircuitBreaker::new(policy));
+//!
+//! let config = ConfigBuilder::new("re_...")
+//!     .circuit_breaker(Arc::clone(&breaker))
+//!     .build();
+//! let resend = Resend::with_config(config);
+//!
+//! // e.g. in a health endpoint
+//! let healthy = breaker.state() != CircuitState::Open;
+//! # assert!(healthy);
+//! ```
+//!
+//! [`ConfigBuilder::circuit_breaker`]: crate::ConfigBuilder::circuit_breaker
+//! [`Error::CircuitOpen`]: crate::Error::CircuitOpen
+
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Configuration of a [`CircuitBreaker`].
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitBreakerPolicy {
+    /// Fraction of failed requests within [`Self::window`] at which the circuit opens, greater
+    /// than `0.0` and at most `1.0`.
+    pub failure_rate: f64,
+    /// Minimum amount of requests within [`Self::window`] before the circuit can open.
+    pub min_requests: u32,
+    /// The sliding window over which the failure rate is calculated.
+    pub window: Duration,
+    /// How long the circuit stays open before letting probe requests through.
+    pub open_duration: Duration,
+    /// Amount of probe requests let through while half-open, all of which have to succeed for
+    /// the circuit to close.
+    pub half_open_probes: u32,
+}
+
+impl Default for CircuitBreakerPolicy {
+    /// Opens when half of at least 10 requests within 30s failed, probes with 1 request after
+    /// 30s.
+    fn default() -> Self {
+        Self {
+            failure_rate: 0.5,
+            min_requests: 10,
+            window: Duration::from_secs(30),
+            open_duration: Duration::from_secs(30),
+            half_open_probes: 1,
+        }
+    }
+}
+
+impl CircuitBreakerPolicy {
+    /// Sets the fraction of failed requests at which the circuit opens.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `failure_rate` is not in `(0.0, 1.0]`, e.g. `NaN`.
+    #[inline]
+    pub const fn with_failure_rate(mut self, failure_rate: f64) -> Self {
+        assert!(
+            failure_rate > 0.0 && failure_rate <= 1.0,
+            "failure_rate should be in (0.0, 1.0]"
+        );
+        self.failure_rate = failure_rate;
+        self
+    }
+
+    /// Sets the minimum amount of requests before the circuit can open.
+    #[inline]
+    pub const fn with_min_requests(mut self, min_requests: u32) -> Self {
+        self.min_requests = min_requests;
+        self
+    }
+
+    /// Sets the sliding window over which the failure rate is calculated.
+    #[inline]
+    pub const fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets how long the circuit stays open before letting probe requests through.
+    #[inline]
+    pub const fn with_open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+
+    /// Sets the amount of probe requests let through while half-open, at least `1`.
+    #[inline]
+    pub const fn with_half_open_probes(mut self, half_open_probes: u32) -> Self {
+        self.half_open_probes = if half_open_probes == 0 {
+            1
+        } else {
+            half_open_probes
+        };
+        self
+    }
+}
+
+/// The state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Requests are sent.
+    Closed,
+    /// Requests fail with [`Error::CircuitOpen`](crate::Error::CircuitOpen) without being sent.
+    Open,
+    /// A limited amount of probe requests are sent to decide whether to close the circuit.
+    HalfOpen,
+}
+
+/// A circuit breaker, see the [module level documentation](self).
+///
+/// The same circuit breaker can be passed to several clients so that they open and close
+/// together.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    policy: CircuitBreakerPolicy,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: State,
+    /// The outcomes of the requests within the window while closed, `true` if failed.
+    outcomes: VecDeque<(Instant, bool)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Closed,
+    /// Open until `until`, or until reset if the open duration doesn't fit in an [`Instant`].
+    Open {
+        until: Option<Instant>,
+    },
+    HalfOpen {
+        in_flight: u32,
+        successes: u32,
+    },
+}
+
+impl CircuitBreaker {
+    /// Creates a new closed [`CircuitBreaker`].
+    pub fn new(policy: CircuitBreakerPolicy) -> Self {
+        Self {
+            policy,
+            inner: Mutex::new(Inner {
+                state: State::Closed,
+                outcomes: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns the [`CircuitBreakerPolicy`] of this circuit breaker.
+    pub const fn policy(&self) -> &CircuitBreakerPolicy {
+        &self.policy
+    }
+
+    /// Returns the current [`CircuitState`].
+    ///
+    /// An open circuit is reported as half-open once [`CircuitBreakerPolicy::open_duration`] has
+    /// passed, as the next request will be let through as a probe.
+    pub fn state(&self) -> CircuitState {
+        let state = self.lock().state;
+        match state {
+            State::Closed => CircuitState::Closed,
+            State::Open { until } if until.is_none_or(|until| Instant::now() < until) => {
+                CircuitState::Open
+            }
+            State::Open { .. } | State::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Closes the circuit and forgets the outcomes of previous requests.
+    pub fn reset(&self) {
+        let mut inner = self.lock();
+        inner.state = State::Closed;
+        inner.outcomes.clear();
+    }
+
+    /// Asks for permission to send a request.
+    ///
+    /// Returns the time until the next probe request may be sent if the circuit is open.
+    pub(crate) fn acquire(&self) -> Result<Permit<'_>, Duration> {
+        let now = Instant::now();
+        let mut inner = self.lock();
+
+        let probe = match inner.state {
+            State::Closed => false,
+            State::Open { until: None } => return Err(Duration::MAX),
+            State::Open { until: Some(until) } if now < until => return Err(until - now),
+            State::Open { .. } => {
+                inner.state = State::HalfOpen {
+                    in_flight: 1,
+                    successes: 0,
+                };
+                true
+            }
+            State::HalfOpen {
+                ref mut in_flight,
+                successes,
+            } if *in_flight + successes < self.policy.half_open_probes => {
+                *in_flight += 1;
+                true
+            }
+            State::HalfOpen { .. } => return Err(Duration::ZERO),
+        };
+        drop(inner);
+
+        Ok(Permit {
+            breaker: self,
+            probe,
+            outcome: Outcome::Ignored,
+        })
+    }
+
+    fn record(&self, probe: bool, outcome: Outcome) {
+        let now = Instant::now();
+        let mut inner = self.lock();
+
+        match inner.state {
+            State::HalfOpen {
+                ref mut in_flight,
+                ref mut successes,
+            } if probe => {
+                *in_flight = in_flight.saturating_sub(1);
+                match outcome {
+                    Outcome::Success => *successes += 1,
+                    Outcome::Failure => self.open(&mut inner, now),
+                    Outcome::Ignored => {}
+                }
+                if matches!(inner.state, State::HalfOpen { successes, .. } if successes >= self.policy.half_open_probes)
+                {
+                    inner.state = State::Closed;
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("circuit breaker closed");
+                }
+            }
+            State::Closed if !probe => {
+                let failed = match outcome {
+                    Outcome::Success => false,
+                    Outcome::Failure => true,
+                    Outcome::Ignored => return,
+                };
+                inner.outcomes.push_back((now, failed));
+                while let Some(&(at, _)) = inner.outcomes.front() {
+                    if now.duration_since(at) <= self.policy.window {
+                        break;
+                    }
+                    let _expired = inner.outcomes.pop_front();
+                }
+
+                let requests = inner.outcomes.len();
+                let failures = inner.outcomes.iter().filter(|(_, failed)| *failed).count();
+                #[allow(clippy::cast_precision_loss)]
+                let failure_rate = failures as f64 / requests as f64;
+                if requests >= self.policy.min_requests as usize
+                    && failure_rate >= self.policy.failure_rate
+                {
+                    self.open(&mut inner, now);
+                }
+            }
+            // Requests sent before the state changed.
+            _ => {}
+        }
+        drop(inner);
+    }
+
+    fn open(&self, inner: &mut Inner, now: Instant) {
+        inner.state = State::Open {
+            until: now.checked_add(self.policy.open_duration),
+        };
+        inner.outcomes.clear();
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(open_duration = ?self.policy.open_duration, "circuit breaker opened");
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The outcome of a request for a [`CircuitBreaker`].
+#[allow(clippy::redundant_pub_crate)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Success,
+    /// A `5xx` response, a timeout or a connection error.
+    Failure,
+    /// Neither, e.g. a `429` response.
+    Ignored,
+}
+
+/// Permission to send a request, the outcome of which is passed to [`Permit::record`].
+///
+/// Dropping the permit without recording an outcome (e.g. because the request was cancelled)
+/// counts as [`Outcome::Ignored`].
+#[allow(clippy::redundant_pub_crate)]
+#[derive(Debug)]
+pub(crate) struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    outcome: Outcome,
+}
+
+impl Permit<'_> {
+    pub(crate) fn record(mut self, outcome: Outcome) {
+        self.outcome = outcome;
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.breaker.record(self.probe, self.outcome);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::time::Duration;
+
+    use super::{CircuitBreaker, CircuitBreakerPolicy, CircuitState, Outcome};
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(
+            CircuitBreakerPolicy::default()
+                .with_min_requests(4)
+                .with_open_duration(Duration::from_millis(50))
+                .with_half_open_probes(2),
+        )
+    }
+
+    #[test]
+    fn opens_and_closes() {
+        let breaker = breaker();
+
+        for outcome in [Outcome::Success, Outcome::Success, Outcome::Ignored] {
+            breaker.acquire().unwrap().record(outcome);
+        }
+        breaker.acquire().unwrap().record(Outcome::Failure);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.acquire().unwrap().record(Outcome::Failure);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.acquire().unwrap_err() > Duration::ZERO);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        let first = breaker.acquire().unwrap();
+        let second = breaker.acquire().unwrap();
+        assert_eq!(breaker.acquire().unwrap_err(), Duration::ZERO);
+
+        first.record(Outcome::Success);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        second.record(Outcome::Success);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_failure() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.acquire().unwrap().record(Outcome::Failure);
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(60));
+        // Dropped probes free their slot
+        drop(breaker.acquire().unwrap());
+        let probe = breaker.acquire().unwrap();
+        let _other = breaker.acquire().unwrap();
+        probe.record(Outcome::Failure);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        breaker.reset();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.acquire().is_ok());
+    }
+
+    #[test]
+    fn huge_open_duration() {
+        let policy = CircuitBreakerPolicy::default()
+            .with_min_requests(1)
+            .with_open_duration(Duration::MAX);
+        let breaker = CircuitBreaker::new(policy);
+
+        breaker.acquire().unwrap().record(Outcome::Failure);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(breaker.acquire().unwrap_err(), Duration::MAX);
+
+        breaker.reset();
+        assert!(breaker.acquire().is_ok());
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn fail_fast() {
+        use std::sync::Arc;
+
+        use crate::{
+            ConfigBuilder, Error, Resend, test::ScriptedTransport, types::ShareEmailOptions,
+        };
+
+        let breaker = Arc::new(breaker());
+        let transport = ScriptedTransport::unavailable();
+        let config = ConfigBuilder::new("re_test")
+            .transport(transport.clone())
+            .circuit_breaker(Arc::clone(&breaker))
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        for _ in 0..4 {
+            let res = resend.emails.share("abc", ShareEmailOptions::new()).await;
+            assert!(matches!(res, Err(Error::Resend { .. })));
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let err = resend
+            .emails
+            .share("abc", ShareEmailOptions::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::CircuitOpen { .. }));
+        assert!(err.is_retryable());
+        assert_eq!(err.context().endpoint.as_deref(), Some("/emails/abc/share"));
+        assert_eq!(transport.calls(), 4);
+    }
+}
@@ -1,14 +1,11 @@
+use std::fmt;
 use std::sync::Arc;
-use std::{env, fmt};
 
-#[cfg(not(feature = "blocking"))]
 use reqwest::Client as ReqwestClient;
-#[cfg(feature = "blocking")]
-use reqwest::blocking::Client as ReqwestClient;
 
 use crate::{
     batch::BatchSvc,
-    config::Config,
+    config::{Config, ConfigBuilder, ConfigError},
     events::EventsSvc,
     logs::LogsSvc,
     oauth::OAuthSvc,
@@ -22,9 +19,6 @@ use crate::{
     topics::TopicsSvc,
 };
 
-#[cfg(doc)]
-use crate::ConfigBuilder;
-
 /// The [Resend](https://resend.com) client.
 #[must_use]
 #[derive(Clone)]
@@ -69,6 +63,8 @@ impl Resend {
     /// ### Panics
     ///
     /// - Panics if the environment variable `RESEND_BASE_URL` is set but is not a valid `URL`.
+    /// - Panics if the environment variable `RESEND_RATE_LIMIT` is set but is not a valid non zero
+    ///   `u32`.
     ///
     /// [`Resend`]: https://resend.com
     pub fn new(api_key: &str) -> Self {
@@ -80,11 +76,13 @@ impl Resend {
     /// ### Panics
     ///
     /// - Panics if the environment variable `RESEND_BASE_URL` is set but is not a valid `URL`.
+    /// - Panics if the environment variable `RESEND_RATE_LIMIT` is set but is not a valid non zero
+    ///   `u32`.
     ///
     /// [`Resend`]: https://resend.com
     /// [`reqwest::Client`]: ReqwestClient
     pub fn with_client(api_key: &str, client: ReqwestClient) -> Self {
-        let config = Config::new(api_key.to_owned(), client, None);
+        let config = ConfigBuilder::new(api_key).client(client).build();
         Self::with_config(config)
     }
 
@@ -121,6 +119,14 @@ impl Resend {
         }
     }
 
+    /// Creates a new [`Resend`] client from the environment variables, returns an error instead
+    /// of panicking if they are missing or invalid.
+    ///
+    /// See [`ConfigBuilder::from_env`] for the variables read.
+    pub fn try_default() -> Result<Self, ConfigError> {
+        Config::from_env().map(Self::with_config)
+    }
+
     /// Returns the reference to the used `User-Agent` header value.
     #[inline]
     #[must_use]
@@ -128,11 +134,13 @@ impl Resend {
         self.config().user_agent.as_str()
     }
 
-    /// Returns the reference to the provided `API key`.
+    /// Returns the `API key` used for the next request.
+    ///
+    /// See [`ConfigBuilder::credentials`] for changing it at runtime.
     #[inline]
     #[must_use]
-    pub fn api_key(&self) -> &str {
-        self.config().api_key.as_ref()
+    pub fn api_key(&self) -> String {
+        self.config().credentials.api_key()
     }
 
     /// Returns the reference to the used `base URL`.
@@ -163,17 +171,20 @@ impl Resend {
 }
 
 impl Default for Resend {
-    /// Creates a new [`Resend`] client from the `RESEND_API_KEY` environment variable .
+    /// Creates a new [`Resend`] client from the `RESEND_API_KEY` (or `RESEND_API_KEY_FILE`)
+    /// environment variable.
     ///
     /// ### Panics
     ///
-    /// - Panics if the environment variable `RESEND_API_KEY` is not set.
+    /// - Panics if neither `RESEND_API_KEY` nor `RESEND_API_KEY_FILE` is set, or the file can't
+    ///   be read.
     /// - Panics if the environment variable `RESEND_BASE_URL` is set but is not a valid `URL`.
+    /// - Panics if the environment variable `RESEND_RATE_LIMIT` is set but is not a valid non zero
+    ///   `u32`.
+    ///
+    /// Use [`Resend::try_default`] to handle these errors instead.
     fn default() -> Self {
-        let api_key = env::var("RESEND_API_KEY")
-            .expect("env variable `RESEND_API_KEY` should be a valid API key");
-
-        Self::new(api_key.as_str())
+        Self::try_default().unwrap_or_else(|error| panic!("{error}"))
     }
 }
 
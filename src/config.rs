@@ -1,21 +1,30 @@
-#[cfg(not(feature = "blocking"))]
-use governor::{
-    Quota, RateLimiter,
-    clock::MonotonicClock,
-    middleware::NoOpMiddleware,
-    state::{InMemoryState, NotKeyed},
-};
-#[cfg(feature = "blocking")]
-use reqwest::blocking::{Client, RequestBuilder, Response};
-#[cfg(not(feature = "blocking"))]
-use reqwest::{Client, RequestBuilder, Response};
+use reqwest::{Client, Request, RequestBuilder, Response};
 use reqwest::{Method, Url};
-use reqwest::{StatusCode, header::USER_AGENT};
-use std::{env, fmt};
-#[cfg(not(feature = "blocking"))]
-use std::{num::NonZeroU32, sync::Arc, time::Duration};
+use reqwest::{
+    StatusCode,
+    header::{AUTHORIZATION, HeaderValue, USER_AGENT},
+};
+use serde::de::DeserializeOwned;
+use std::{env, fmt, io, num::ParseIntError, path::PathBuf};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use crate::{Error, Result, error::types::ErrorResponse};
+use crate::{
+    Error, Result,
+    cache::Cache,
+    cassette::{Cassette, Player, Recorder},
+    circuit_breaker::{CircuitBreaker, Outcome},
+    credentials::CredentialProvider,
+    error::types::{ErrorKind, ErrorResponse},
+    interceptor::{Action, Interceptor},
+    meta::WithMeta,
+    mode::Mode,
+    rate_limit::{Limiter, Quota, QuotaLimiter, RateLimitHeaders, RetryPolicy},
+    request_opts::{IDEMPOTENCY_KEY, RequestOptions},
+    transport::Transport,
+};
 
 #[cfg(doc)]
 use crate::Resend;
@@ -47,8 +56,104 @@ use crate::Resend;
 #[non_exhaustive]
 pub struct ConfigBuilder {
     api_key: String,
+    credentials: Option<Arc<dyn CredentialProvider>>,
     base_url: Option<Url>,
     client: Option<Client>,
+    transport: Option<Arc<dyn Transport>>,
+    retry_policy: Option<RetryPolicy>,
+    cassette: Option<CassetteMode>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    rate_limit: Option<RateLimit>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    cache: Option<Arc<Cache>>,
+    mode: Mode,
+}
+
+/// Client side rate limiting set on a [`ConfigBuilder`].
+#[allow(clippy::redundant_pub_crate)]
+#[derive(Debug, Clone)]
+pub(crate) enum RateLimit {
+    Disabled,
+    Limiter(Arc<dyn Limiter>),
+}
+
+/// An invalid configuration, returned by [`ConfigBuilder::from_env`] and
+/// [`ConfigBuilder::try_build`].
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// Neither `RESEND_API_KEY` nor `RESEND_API_KEY_FILE` is set.
+    #[error("env variable `RESEND_API_KEY` or `RESEND_API_KEY_FILE` should be set")]
+    MissingApiKey,
+
+    /// An environment variable is set but is not valid unicode.
+    #[error("env variable `{name}` should be valid unicode")]
+    NotUnicode { name: &'static str },
+
+    /// The file holding the API key can't be read.
+    #[error("API key file `{}` could not be read", path.display())]
+    ApiKeyFile {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// The file holding the API key is empty.
+    #[error("API key file `{}` is empty", path.display())]
+    EmptyApiKeyFile { path: PathBuf },
+
+    /// The base url is not a valid URL.
+    #[error("base url `{value}` should be a valid URL")]
+    InvalidBaseUrl {
+        value: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// The rate limit is not a valid non zero `u32`.
+    #[error("rate limit `{value}` should be a valid non zero u32")]
+    InvalidRateLimit {
+        value: String,
+        #[source]
+        source: ParseIntError,
+    },
+
+    /// The cassette set with [`ConfigBuilder::replay`] can't be read.
+    #[error("cassette `{}` could not be read", path.display())]
+    Cassette {
+        path: PathBuf,
+        #[source]
+        source: Error,
+    },
+
+    /// The profile file can't be read.
+    #[error("profile file `{}` could not be read", path.display())]
+    ProfileFile {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// The profile file is not valid TOML or contains unknown settings.
+    #[error("profile file `{}` is invalid", path.display())]
+    InvalidProfileFile {
+        path: PathBuf,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// The requested profile is not defined in the profile file.
+    #[error("profile `{name}` is not defined in `{}`", path.display())]
+    UnknownProfile { path: PathBuf, name: String },
+}
+
+/// Looks up an environment variable, e.g. [`env::var`].
+type Vars<'a> = &'a dyn Fn(&'static str) -> Result<String, env::VarError>;
+
+#[derive(Debug, Clone)]
+enum CassetteMode {
+    Record(PathBuf),
+    Replay(PathBuf),
 }
 
 impl ConfigBuilder {
@@ -59,11 +164,67 @@ impl ConfigBuilder {
     {
         Self {
             api_key: api_key.into(),
+            credentials: None,
             base_url: None,
             client: None,
+            transport: None,
+            retry_policy: None,
+            cassette: None,
+            interceptors: Vec::new(),
+            rate_limit: None,
+            circuit_breaker: None,
+            cache: None,
+            mode: Mode::Live,
         }
     }
 
+    /// Create new [`ConfigBuilder`] whose API key is supplied by `credentials` for every
+    /// request, see [`ConfigBuilder::credentials`].
+    pub fn with_credentials(credentials: impl CredentialProvider + 'static) -> Self {
+        Self::new(String::new()).credentials(credentials)
+    }
+
+    /// Create new [`ConfigBuilder`] from the environment variables.
+    ///
+    /// - The API key is read from `RESEND_API_KEY`. If that is not set, it is read from the file
+    ///   at the path in `RESEND_API_KEY_FILE` instead, e.g. a Docker or Kubernetes secret.
+    /// - The base url is read from `RESEND_BASE_URL` (optional).
+    /// - The rate limit burst is read from `RESEND_RATE_LIMIT` (optional).
+    ///
+    /// ```no_run
+    /// # fn run() -> Result<(), resend_rs::ConfigError> {
+    /// use resend_rs::{ConfigBuilder, Resend};
+    ///
+    /// let config = ConfigBuilder::from_env()?.try_build()?;
+    /// let resend = Resend::with_config(config);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_vars(&env::var::<&str>)
+    }
+
+    fn from_vars(vars: Vars<'_>) -> Result<Self, ConfigError> {
+        let mut builder = Self::new(api_key_from_vars(vars)?);
+        builder.base_url = Some(base_url_from_vars(vars)?);
+        builder.rate_limit = Some(RateLimit::Limiter(Arc::new(QuotaLimiter::new(
+            quota_from_vars(vars)?,
+        ))));
+        Ok(builder)
+    }
+
+    /// Set a [`CredentialProvider`] that supplies the API key for every request, instead of the
+    /// fixed one passed to [`ConfigBuilder::new`]. [`ConfigBuilder::with_credentials`] does
+    /// without the fixed API key.
+    ///
+    /// See [`ApiKeyCell`](crate::credentials::ApiKeyCell) for rotating the API key of every
+    /// client at once.
+    #[must_use]
+    pub fn credentials(mut self, credentials: impl CredentialProvider + 'static) -> Self {
+        self.credentials = Some(Arc::new(credentials));
+        self
+    }
+
     /// Set a custom Resend's base url.
     ///
     /// This can be your proxy's url (if any) or a test server url which
@@ -86,9 +247,222 @@ impl ConfigBuilder {
         self
     }
 
+    /// Set a custom [`Transport`] that dispatches the requests.
+    ///
+    /// Requests are still built with the http client (see [`ConfigBuilder::client`]) but are
+    /// executed by `transport`. If not provided here, the http client is used.
+    #[must_use]
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Set the [`RetryPolicy`] applied to every request.
+    ///
+    /// If not provided here, requests are not retried.
+    #[must_use]
+    pub const fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Limit the requests sent by the client to `quota`.
+    ///
+    /// If not provided here, the default [`Quota`] is used with the burst taken from the
+    /// `RESEND_RATE_LIMIT` environment variable (if set).
+    ///
+    /// The quota adapts to the rate limit headers returned by Resend, see [`QuotaLimiter`]. Use
+    /// [`ConfigBuilder::limiter`] with a non adaptive [`QuotaLimiter`] to enforce a fixed quota.
+    #[must_use]
+    pub fn rate_limit(self, quota: Quota) -> Self {
+        self.limiter(Arc::new(QuotaLimiter::new(quota)))
+    }
+
+    /// Set the [`Limiter`] awaited before every request.
+    ///
+    /// The same limiter can be passed to several clients using the same API key so that they
+    /// share one quota.
+    #[must_use]
+    pub fn limiter(mut self, limiter: Arc<dyn Limiter>) -> Self {
+        self.rate_limit = Some(RateLimit::Limiter(limiter));
+        self
+    }
+
+    /// Disable client side rate limiting.
+    #[must_use]
+    pub fn without_rate_limit(mut self) -> Self {
+        self.rate_limit = Some(RateLimit::Disabled);
+        self
+    }
+
+    /// Fail requests fast with [`Error::CircuitOpen`] while Resend is unavailable, see the
+    /// [`circuit_breaker`](crate::circuit_breaker) module.
+    ///
+    /// The same circuit breaker can be passed to several clients so that they share its state.
+    #[must_use]
+    pub fn circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Answer `GET` requests to slow-changing resources from a [`Cache`], see the
+    /// [`cache`](crate::cache) module.
+    ///
+    /// The same cache can be passed to several clients of the same team so that they share it.
+    #[must_use]
+    pub fn cache(mut self, cache: Arc<Cache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Set the [`Mode`] deciding whether emails are sent, e.g. [`Mode::DryRun`] for
+    /// non-production environments. See the [`mode`](crate::mode) module for details.
+    ///
+    /// If not provided here, emails are sent.
+    #[must_use]
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Append an [`Interceptor`] to the chain run around every request.
+    ///
+    /// Interceptors see requests in the order they were added and responses in the reverse
+    /// order. See the [`interceptor`](crate::interceptor) module for details.
+    #[must_use]
+    pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Record every request and response to the cassette file at `path`.
+    ///
+    /// The file is overwritten once the client and all its clones are dropped, use a
+    /// [`Recorder`] with [`ConfigBuilder::transport`] to write it earlier with
+    /// [`Recorder::save`]. See the [`cassette`](crate::cassette) module for details.
+    #[must_use]
+    pub fn record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cassette = Some(CassetteMode::Record(path.into()));
+        self
+    }
+
+    /// Serve the responses recorded in the cassette file at `path` instead of making requests.
+    ///
+    /// See the [`cassette`](crate::cassette) module for details.
+    #[must_use]
+    pub fn replay(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cassette = Some(CassetteMode::Replay(path.into()));
+        self
+    }
+
     /// Builder's terminal method producing [`Config`].
+    ///
+    /// ### Panics
+    ///
+    /// Panics in the cases [`ConfigBuilder::try_build`] returns an error for.
     pub fn build(self) -> Config {
-        Config::new(self.api_key, self.client.unwrap_or_default(), self.base_url)
+        self.try_build().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Builder's terminal method producing [`Config`], returns an error instead of panicking if:
+    ///
+    /// - A cassette is set for [`ConfigBuilder::replay`] and it can't be read.
+    /// - No base url has been set and the environment variable `RESEND_BASE_URL` is set but is
+    ///   not a valid `URL`.
+    /// - No rate limit has been set and the environment variable `RESEND_RATE_LIMIT` is set but
+    ///   is not a valid non zero `u32`.
+    pub fn try_build(self) -> Result<Config, ConfigError> {
+        let mut config = Config::try_new(
+            self.api_key,
+            self.client.unwrap_or_default(),
+            self.base_url,
+            self.rate_limit,
+        )?;
+        if let Some(credentials) = self.credentials {
+            config.credentials = credentials;
+        }
+        if let Some(transport) = self.transport {
+            config.transport = transport;
+        }
+        if let Some(retry_policy) = self.retry_policy {
+            config.retry_policy = retry_policy;
+        }
+        config.interceptors = self.interceptors;
+        config.circuit_breaker = self.circuit_breaker;
+        config.cache = self.cache;
+        config.mode = self.mode;
+        match self.cassette {
+            Some(CassetteMode::Record(path)) => {
+                let recorder = Recorder::new(config.transport, path);
+                config.transport = Arc::new(recorder);
+            }
+            Some(CassetteMode::Replay(path)) => {
+                let cassette = Cassette::load(&path)
+                    .map_err(|source| ConfigError::Cassette { path, source })?;
+                config.transport = Arc::new(Player::new(cassette));
+            }
+            None => {}
+        }
+        Ok(config)
+    }
+}
+
+/// Reads the API key from `RESEND_API_KEY` or else from the file at `RESEND_API_KEY_FILE`.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn api_key_from_vars(vars: Vars<'_>) -> Result<String, ConfigError> {
+    if let Some(api_key) = var(vars, "RESEND_API_KEY")? {
+        return Ok(api_key);
+    }
+
+    let path = var(vars, "RESEND_API_KEY_FILE")?.ok_or(ConfigError::MissingApiKey)?;
+    api_key_from_file(PathBuf::from(path))
+}
+
+/// Reads the API key from the file at `path`, ignoring surrounding whitespace.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn api_key_from_file(path: PathBuf) -> Result<String, ConfigError> {
+    match std::fs::read_to_string(&path) {
+        Ok(api_key) if api_key.trim().is_empty() => Err(ConfigError::EmptyApiKeyFile { path }),
+        Ok(api_key) => Ok(api_key.trim().to_owned()),
+        Err(source) => Err(ConfigError::ApiKeyFile { path, source }),
+    }
+}
+
+/// Reads the base url from `RESEND_BASE_URL`, falling back to the default one.
+fn base_url_from_vars(vars: Vars<'_>) -> Result<Url, ConfigError> {
+    var(vars, "RESEND_BASE_URL")?.map_or_else(
+        || Ok(Url::parse("https://api.resend.com").expect("default base url should be valid")),
+        parse_base_url,
+    )
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn parse_base_url(value: String) -> Result<Url, ConfigError> {
+    Url::parse(&value).map_err(|source| ConfigError::InvalidBaseUrl {
+        value,
+        source: Box::new(source),
+    })
+}
+
+/// The default quota with the burst taken from `RESEND_RATE_LIMIT`.
+fn quota_from_vars(vars: Vars<'_>) -> Result<Quota, ConfigError> {
+    let quota = Quota::default();
+    let Some(value) = var(vars, "RESEND_RATE_LIMIT")? else {
+        return Ok(quota);
+    };
+
+    match value.parse() {
+        Ok(burst) => Ok(quota.with_burst(burst)),
+        Err(source) => Err(ConfigError::InvalidRateLimit { value, source }),
+    }
+}
+
+/// Looks up `name`, treating unset variables as `None`.
+fn var(vars: Vars<'_>, name: &'static str) -> Result<Option<String>, ConfigError> {
+    match vars(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(env::VarError::NotUnicode(_)) => Err(ConfigError::NotUnicode { name }),
     }
 }
 
@@ -99,18 +473,17 @@ impl ConfigBuilder {
 #[derive(Clone)]
 pub struct Config {
     pub(crate) user_agent: String,
-    pub(crate) api_key: String,
+    pub(crate) credentials: Arc<dyn CredentialProvider>,
     pub(crate) base_url: Url,
     pub(crate) client: Client,
-    #[cfg(not(feature = "blocking"))]
-    limiter: Arc<
-        RateLimiter<
-            NotKeyed,
-            InMemoryState,
-            MonotonicClock,
-            NoOpMiddleware<<MonotonicClock as governor::clock::Clock>::Instant>,
-        >,
-    >,
+    pub(crate) transport: Arc<dyn Transport>,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) interceptors: Vec<Arc<dyn Interceptor>>,
+    pub(crate) limiter: Option<Arc<dyn Limiter>>,
+    pub(crate) circuit_breaker: Option<Arc<CircuitBreaker>>,
+    pub(crate) cache: Option<Arc<Cache>>,
+    pub(crate) request_options: RequestOptions,
+    pub(crate) mode: Mode,
 }
 
 impl Config {
@@ -124,48 +497,59 @@ impl Config {
         ConfigBuilder::new(api_key.into())
     }
 
+    /// Creates a new [`Config`] from the environment variables, see [`ConfigBuilder::from_env`].
+    pub fn from_env() -> Result<Self, ConfigError> {
+        ConfigBuilder::from_env()?.try_build()
+    }
+
     /// Creates a new [`Config`].
     ///
     /// Note: the `base_url` parameter takes presedence over the `RESEND_BASE_URL` environment
-    /// variable.
-    #[must_use]
-    pub(crate) fn new(api_key: String, client: Client, base_url: Option<Url>) -> Self {
-        let env_base_url = base_url.unwrap_or_else(|| {
-            env::var("RESEND_BASE_URL")
-                .map_or_else(
-                    |_| Url::parse("https://api.resend.com"),
-                    |env_var| Url::parse(env_var.as_str()),
-                )
-                .expect("env variable `RESEND_BASE_URL` should be a valid URL")
-        });
+    /// variable and the `rate_limit` parameter over the `RESEND_RATE_LIMIT` one.
+    fn try_new(
+        api_key: String,
+        client: Client,
+        base_url: Option<Url>,
+        rate_limit: Option<RateLimit>,
+    ) -> Result<Self, ConfigError> {
+        let vars: Vars<'_> = &env::var::<&str>;
+        let env_base_url = match base_url {
+            Some(base_url) => base_url,
+            None => base_url_from_vars(vars)?,
+        };
 
         let env_user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
 
-        // ==== Rate limiting is a non-blocking thing only ====
-        #[cfg(not(feature = "blocking"))]
-        let rate_limit_per_sec = env::var("RESEND_RATE_LIMIT")
-            .unwrap_or_else(|_| "9".to_owned())
-            .parse::<u32>()
-            .expect("env variable `RESEND_RATE_LIMIT` should be a valid u32");
-
-        #[cfg(not(feature = "blocking"))]
-        let quota = Quota::with_period(Duration::from_millis(1100))
-            .expect("Valid quota")
-            .allow_burst(
-                NonZeroU32::new(rate_limit_per_sec).expect("Rate limit is a valid non zero u32"),
-            );
-
-        #[cfg(not(feature = "blocking"))]
-        let limiter = Arc::new(RateLimiter::direct_with_clock(quota, MonotonicClock));
-        // ====================================================
+        let limiter = match rate_limit {
+            None => {
+                let limiter: Arc<dyn Limiter> = Arc::new(QuotaLimiter::new(quota_from_vars(vars)?));
+                Some(limiter)
+            }
+            Some(RateLimit::Disabled) => None,
+            Some(RateLimit::Limiter(limiter)) => Some(limiter),
+        };
 
-        Self {
+        Ok(Self {
             user_agent: env_user_agent,
-            api_key,
+            credentials: Arc::new(api_key),
             base_url: env_base_url,
+            transport: Arc::new(client.clone()),
             client,
-            #[cfg(not(feature = "blocking"))]
+            retry_policy: RetryPolicy::disabled(),
+            interceptors: Vec::new(),
             limiter,
+            circuit_breaker: None,
+            cache: None,
+            request_options: RequestOptions::default(),
+            mode: Mode::Live,
+        })
+    }
+
+    /// Returns a copy of this [`Config`] that applies `options` to every request.
+    pub(crate) fn with_options(&self, options: RequestOptions) -> Self {
+        Self {
+            request_options: options,
+            ..self.clone()
         }
     }
 
@@ -176,50 +560,267 @@ impl Config {
             .join(path)
             .expect("should be a valid API endpoint");
 
-        self.client
+        // Only the requests creating or changing something take the idempotency key of the
+        // options, not the reads (e.g. the pages of a `list_all`).
+        let writes = method == Method::POST || method == Method::PATCH;
+
+        let request = self
+            .client
             .request(method, path)
-            .bearer_auth(self.api_key.as_str())
-            .header(USER_AGENT, self.user_agent.as_str())
+            .bearer_auth(self.credentials.api_key())
+            .header(USER_AGENT, self.user_agent.as_str());
+
+        match self.request_options.idempotency_key() {
+            Some(idempotency_key) if writes => request.header(IDEMPOTENCY_KEY, idempotency_key),
+            _ => request,
+        }
     }
 
+    /// Sends the request, retrying it according to the configured [`RetryPolicy`].
     #[allow(unreachable_pub)]
-    #[maybe_async::maybe_async]
     pub async fn send(&self, request: RequestBuilder) -> Result<Response> {
-        #[cfg(not(feature = "blocking"))]
+        self.send_request(request.build()?).await
+    }
+
+    /// Sends the request and deserializes the body of its successful response.
+    pub(crate) async fn send_json<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T> {
+        Ok(self.send_json_with_meta(request).await?.data)
+    }
+
+    /// The same as [`Config::send_json`] but also returns the [`ResponseMeta`] of the response.
+    ///
+    /// A body that can't be deserialized is an [`Error::Parse`] with the status, request id,
+    /// method and endpoint of the response.
+    ///
+    /// [`ResponseMeta`]: crate::meta::ResponseMeta
+    pub(crate) async fn send_json_with_meta<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<WithMeta<T>> {
+        let request = request.build()?;
+        let method = request.method().clone();
+        let endpoint = request.url().path().to_owned();
+
+        let response = self.send_request(request).await?;
+        WithMeta::from_response(response)
+            .await
+            .map_err(|error| error.with_request(&method, &endpoint))
+    }
+
+    async fn send_request(&self, mut request: Request) -> Result<Response> {
+        self.request_options.apply(&mut request);
+        let method = request.method().clone();
+        let endpoint = request.url().path().to_owned();
+
+        // Before anything that may rewrite the url (interceptors) or fail the request (circuit
+        // breaker, rate limit), so that emails are never sent by accident.
+        let relative = self.endpoint(request.url()).to_owned();
+
+        // Before the mode and the cache, so that the requests they answer are traced too.
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "resend.request",
+            http.request.method = %method,
+            http.route = route(&relative),
+            http.response.status_code = tracing::field::Empty,
+            dry_run = tracing::field::Empty,
+            cache_hit = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+            retries = tracing::field::Empty,
+            ratelimit.limit = tracing::field::Empty,
+            ratelimit.remaining = tracing::field::Empty,
+            ratelimit.reset = tracing::field::Empty,
+        );
+
+        let sending = self.mode.apply(&relative, &mut request);
+        let sending = sending.map_err(|error| error.with_request(&method, &endpoint))?;
+        #[cfg(feature = "tracing")]
+        let _span = span.record("dry_run", sending.is_some());
+        if let Some(response) = sending {
+            #[cfg(feature = "tracing")]
+            let _span = span.record("http.response.status_code", response.status().as_u16());
+            return Ok(response);
+        }
+
+        let cached = self.cache.as_ref().and_then(|cache| {
+            let credential = request.headers().get(AUTHORIZATION);
+            let credential = credential.map_or(&[][..], HeaderValue::as_bytes);
+            let url = request.url();
+            cache
+                .begin(&method, credential, url, self.endpoint(url))
+                .map(|pending| (cache, pending))
+        });
+        if let Some((cache, ref pending)) = cached {
+            let hit = cache.get(pending);
+            #[cfg(feature = "tracing")]
+            let _span = span.record("cache_hit", hit.is_some());
+            if let Some(response) = hit {
+                #[cfg(feature = "tracing")]
+                let _span = span.record("http.response.status_code", response.status().as_u16());
+                return Ok(response);
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        let response = tracing::Instrument::instrument(self.send_with_retries(request), span).await;
+
+        #[cfg(not(feature = "tracing"))]
+        let response = self.send_with_retries(request).await;
+
+        let response = response.map_err(|error| error.with_request(&method, &endpoint));
+        match cached {
+            Some((cache, pending)) => cache.finish(pending, response).await,
+            None => response,
+        }
+    }
+
+    /// Returns the path of `url` relative to the base url.
+    fn endpoint<'a>(&self, url: &'a Url) -> &'a str {
+        let path = url.path();
+        path.strip_prefix(self.base_url.path().trim_end_matches('/'))
+            .unwrap_or(path)
+    }
+
+    /// Sends the already built request, retrying it according to the configured [`RetryPolicy`].
+    async fn send_with_retries(&self, request: Request) -> Result<Response> {
+        #[cfg(feature = "tracing")]
+        let start = Instant::now();
+
+        let mut request = request;
+        let mut attempt = 0;
+        let deadline = self
+            .request_options
+            .deadline()
+            .and_then(|deadline| Instant::now().checked_add(deadline));
+
+        let result = loop {
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let timeout = request.timeout().map_or(remaining, |&t| t.min(remaining));
+                *request.timeout_mut() = Some(timeout);
+            }
+
+            // Requests with streaming bodies can't be cloned and thus can't be retried.
+            let retry = (!self.request_options.skip_retry()
+                && attempt < self.retry_policy.max_retries)
+                .then(|| request.try_clone())
+                .flatten();
+
+            let Some(retry) = retry else {
+                break self
+                    .send_once(request)
+                    .await
+                    .map_err(|failure| failure.error);
+            };
+
+            match self.send_once(request).await {
+                Ok(response) => break Ok(response),
+                Err(failure) if failure.retryable => {
+                    let delay = self.retry_policy.delay(attempt, failure.retry_after);
+                    if let Some(deadline) = deadline
+                        && Instant::now()
+                            .checked_add(delay)
+                            .is_none_or(|end| end >= deadline)
+                    {
+                        break Err(failure.error);
+                    }
+
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(attempt, ?delay, error = %failure.error, "retrying request");
+
+                    crate::rate_limit::sleep(delay).await;
+
+                    attempt += 1;
+                    request = retry;
+                }
+                Err(failure) => break Err(failure.error),
+            }
+        };
+
+        #[cfg(feature = "tracing")]
         {
-            let jitter =
-                governor::Jitter::new(Duration::from_millis(10), Duration::from_millis(50));
-            self.limiter.until_ready_with_jitter(jitter).await;
+            let span = tracing::Span::current();
+            let _span = span
+                .record("retries", attempt)
+                .record("latency_ms", start.elapsed().as_millis());
+            if let Err(ref error) = result {
+                tracing::debug!(%error, "request failed");
+            }
         }
 
-        let request = request.build()?;
+        result
+    }
 
-        let response = self.client.execute(request).await?;
+    /// Sends the request exactly once.
+    async fn send_once(&self, request: Request) -> Result<Response, Failure> {
+        if let Some(ref limiter) = self.limiter
+            && !self.request_options.skip_rate_limit()
+        {
+            limiter.until_ready().await.map_err(|error| Failure {
+                error,
+                retryable: false,
+                retry_after: None,
+            })?;
+        }
+
+        // Only after the rate limit, so that probes of a half-open circuit are sent right away.
+        let permit = match self.circuit_breaker {
+            Some(ref circuit_breaker) => {
+                let permit = circuit_breaker.acquire().map_err(|retry_after| Failure {
+                    error: Error::CircuitOpen {
+                        retry_after,
+                        context: Box::default(),
+                    },
+                    retryable: false,
+                    retry_after: None,
+                })?;
+                Some(permit)
+            }
+            None => None,
+        };
+
+        let idempotent = self
+            .retry_policy
+            .retries(request.method(), request.headers());
+
+        let response = self.execute(request).await;
+        if let Some(permit) = permit {
+            permit.record(outcome(&response));
+        }
 
-        match response.status() {
+        let response = response.map_err(|error| Failure {
+            retryable: idempotent && is_transient(&error),
+            error,
+            retry_after: None,
+        })?;
+
+        let rate_limit = RateLimitHeaders::from_headers(response.headers());
+
+        #[cfg(feature = "tracing")]
+        record_response(&response, &rate_limit);
+
+        if let Some(ref limiter) = self.limiter {
+            limiter.update(&rate_limit);
+        }
+
+        let status = response.status();
+        let retry_after = header_u64(&response, "retry-after").map(Duration::from_secs);
+        let request_id = response
+            .headers()
+            .get("x-request-id")
+            .and_then(|v| v.to_str().ok())
+            .map(ToOwned::to_owned);
+
+        match status {
             StatusCode::TOO_MANY_REQUESTS => {
-                let headers = response.headers();
-
-                let ratelimit_limit = headers
-                    .get("ratelimit-limit")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok());
-                let ratelimit_remaining = headers
-                    .get("ratelimit-remaining")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok());
-                let ratelimit_reset = headers
-                    .get("ratelimit-reset")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok());
-
-                Err(Error::RateLimit {
-                    ratelimit_limit,
-                    ratelimit_remaining,
-                    ratelimit_reset,
-                })
+                Err(too_many_requests(response, &rate_limit, retry_after, request_id).await)
             }
             x if x.is_client_error() || x.is_server_error() => {
+                let retryable = idempotent && x.is_server_error();
+
                 // TODO: Make this more testable
                 let content_type_is_html = response
                     .headers()
@@ -227,28 +828,236 @@ impl Config {
                     .and_then(|el| el.to_str().ok())
                     .is_some_and(|content_type| content_type.contains("html"));
 
-                if content_type_is_html {
-                    return Err(Error::Parse {
-                        message: response.text().await?,
-                        source: None,
-                    });
-                }
-
                 let error_raw = response.text().await?;
-                let error = serde_json::from_str::<ErrorResponse>(&error_raw).map_err(|e| {
+
+                let error = if content_type_is_html {
                     Error::Parse {
                         message: error_raw,
-                        source: Some(Box::new(e)),
+                        status: Some(x.as_u16()),
+                        source: None,
+                        context: Box::default(),
                     }
-                })?;
+                } else {
+                    match serde_json::from_str::<ErrorResponse>(&error_raw) {
+                        Ok(error) => Error::from(error),
+                        Err(e) => Error::Parse {
+                            message: error_raw,
+                            status: Some(x.as_u16()),
+                            source: Some(Box::new(e)),
+                            context: Box::default(),
+                        },
+                    }
+                };
 
-                Err(Error::Resend(error))
+                Err(Failure {
+                    error: error.with_request_id(request_id),
+                    retryable,
+                    retry_after,
+                })
             }
             _ => Ok(response),
         }
     }
 }
 
+/// Turns a `429` response into an [`Error::RateLimit`] to retry after the reset, or into an
+/// [`Error::Resend`] that isn't retried for a spent daily or monthly quota.
+async fn too_many_requests(
+    response: Response,
+    rate_limit: &RateLimitHeaders,
+    retry_after: Option<Duration>,
+    request_id: Option<String>,
+) -> Failure {
+    let body = response.text().await.unwrap_or_default();
+    let quota = serde_json::from_str::<ErrorResponse>(&body)
+        .ok()
+        .filter(|error| {
+            matches!(
+                error.kind(),
+                ErrorKind::DailyQuotaExceeded | ErrorKind::MonthlyQuotaExceeded
+            )
+        });
+
+    match quota {
+        Some(error) => Failure {
+            error: Error::from(error).with_request_id(request_id),
+            retryable: false,
+            retry_after: None,
+        },
+        None => Failure {
+            error: Error::RateLimit {
+                ratelimit_limit: rate_limit.limit,
+                ratelimit_remaining: rate_limit.remaining,
+                ratelimit_reset: rate_limit.reset,
+                context: Box::default(),
+            }
+            .with_request_id(request_id),
+            retryable: true,
+            retry_after: rate_limit.reset.map(Duration::from_secs).or(retry_after),
+        },
+    }
+}
+
+/// Classifies the outcome of a request for the [`CircuitBreaker`].
+fn outcome(response: &Result<Response>) -> Outcome {
+    match response {
+        Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => Outcome::Ignored,
+        Ok(response) if response.status().is_server_error() => Outcome::Failure,
+        Ok(_) => Outcome::Success,
+        Err(error) if is_transient(error) => Outcome::Failure,
+        Err(_) => Outcome::Ignored,
+    }
+}
+
+/// Returns whether the request failed without a response due to a connection error or a timeout.
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::Http { source, .. } => source.is_timeout() || is_connect(source),
+        Error::Transport { .. } => true,
+        _ => false,
+    }
+}
+
+impl Config {
+    /// Runs `request` through the interceptors and the transport.
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let mut outcome = Ok(Action::Continue(request));
+        let mut ran = 0;
+
+        for interceptor in &self.interceptors {
+            let Ok(Action::Continue(request)) = outcome else {
+                break;
+            };
+            outcome = interceptor.on_request(request).await;
+            ran += 1;
+        }
+
+        let mut response = match outcome {
+            Ok(Action::Continue(request)) => self.transport.execute(request).await,
+            Ok(Action::Respond(response)) => Ok(response),
+            Err(error) => Err(error),
+        };
+
+        for interceptor in self.interceptors.iter().take(ran).rev() {
+            response = interceptor.on_response(response).await;
+        }
+
+        response
+    }
+}
+
+/// A failed attempt of [`Config::send_once`].
+struct Failure {
+    error: Error,
+    /// Whether the request may succeed if retried.
+    retryable: bool,
+    /// Delay requested by the server before retrying.
+    retry_after: Option<Duration>,
+}
+
+impl From<reqwest::Error> for Failure {
+    fn from(error: reqwest::Error) -> Self {
+        Self {
+            error: Error::from(error),
+            retryable: false,
+            retry_after: None,
+        }
+    }
+}
+
+/// Records the status code and rate limit headers of `response` on the current span.
+#[cfg(feature = "tracing")]
+fn record_response(response: &Response, rate_limit: &RateLimitHeaders) {
+    let span = tracing::Span::current();
+    let _span = span.record("http.response.status_code", response.status().as_u16());
+
+    for (field, value) in [
+        ("ratelimit.limit", rate_limit.limit),
+        ("ratelimit.remaining", rate_limit.remaining),
+        ("ratelimit.reset", rate_limit.reset),
+    ] {
+        if let Some(value) = value {
+            let _span = span.record(field, value);
+        }
+    }
+}
+
+/// Returns the endpoint template of `path`, replacing ids (and emails, aliases, ...) with `{id}`
+/// so that it can be used as a low cardinality label.
+#[cfg(any(feature = "tracing", test))]
+fn route(path: &str) -> String {
+    /// Every non-id path segment of the API.
+    const SEGMENTS: &[&str] = &[
+        "add",
+        "api-keys",
+        "attachments",
+        "audiences",
+        "automations",
+        "batch",
+        "broadcasts",
+        "cancel",
+        "claim",
+        "clicked-links",
+        "contact-properties",
+        "contacts",
+        "domains",
+        "duplicate",
+        "emails",
+        "events",
+        "grants",
+        "imports",
+        "logs",
+        "metrics",
+        "oauth",
+        "publish",
+        "receiving",
+        "recipients",
+        "remove",
+        "runs",
+        "segments",
+        "send",
+        "share",
+        "stop",
+        "suppressions",
+        "templates",
+        "topics",
+        "verify",
+        "webhooks",
+    ];
+
+    path.split('/')
+        .map(|segment| {
+            if segment.is_empty() || SEGMENTS.contains(&segment) {
+                segment
+            } else {
+                "{id}"
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Parses the header `name` of `response` as a [`u64`].
+fn header_u64(response: &Response, name: &str) -> Option<u64> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+}
+
+#[cfg(not(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none"))))]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn is_connect(error: &reqwest::Error) -> bool {
+    error.is_connect()
+}
+
+#[cfg(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none")))]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const fn is_connect(_error: &reqwest::Error) -> bool {
+    false
+}
+
 impl fmt::Debug for Config {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Don't output API key.
@@ -259,3 +1068,111 @@ impl fmt::Debug for Config {
             .finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::{collections::HashMap, env::VarError};
+
+    use super::{ConfigBuilder, ConfigError, route};
+    use crate::Resend;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<ConfigBuilder, ConfigError> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        ConfigBuilder::from_vars(&|name| {
+            vars.get(name)
+                .map(|&value| value.to_owned())
+                .ok_or(VarError::NotPresent)
+        })
+    }
+
+    #[test]
+    fn from_env() {
+        let resend =
+            Resend::with_config(from_vars(&[("RESEND_API_KEY", "re_env")]).unwrap().build());
+        assert_eq!(resend.api_key(), "re_env");
+        assert_eq!(resend.base_url(), "https://api.resend.com/");
+
+        let path = std::env::temp_dir().join(format!("resend-api-key-{}", std::process::id()));
+        std::fs::write(&path, "  re_file\n").unwrap();
+        let vars = [
+            ("RESEND_API_KEY_FILE", path.to_str().unwrap()),
+            ("RESEND_BASE_URL", "http://localhost:8080"),
+            ("RESEND_RATE_LIMIT", "5"),
+        ];
+        let resend = Resend::with_config(from_vars(&vars).unwrap().build());
+        assert_eq!(resend.api_key(), "re_file");
+        assert_eq!(resend.base_url(), "http://localhost:8080/");
+
+        std::fs::write(&path, "\n").unwrap();
+        let res = from_vars(&[("RESEND_API_KEY_FILE", path.to_str().unwrap())]);
+        assert!(matches!(res, Err(ConfigError::EmptyApiKeyFile { .. })));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn from_env_errors() {
+        assert!(matches!(from_vars(&[]), Err(ConfigError::MissingApiKey)));
+
+        let res = from_vars(&[("RESEND_API_KEY_FILE", "/nonexistent/resend")]);
+        assert!(matches!(res, Err(ConfigError::ApiKeyFile { .. })));
+
+        let res = from_vars(&[("RESEND_API_KEY", "re"), ("RESEND_BASE_URL", "resend")]);
+        assert!(matches!(res, Err(ConfigError::InvalidBaseUrl { value, .. }) if value == "resend"));
+
+        let res = from_vars(&[("RESEND_API_KEY", "re"), ("RESEND_RATE_LIMIT", "0")]);
+        assert!(matches!(res, Err(ConfigError::InvalidRateLimit { value, .. }) if value == "0"));
+
+        let res = ConfigBuilder::new("re")
+            .replay("/nonexistent/cassette.json")
+            .try_build();
+        assert!(matches!(res, Err(ConfigError::Cassette { .. })));
+    }
+
+    #[test]
+    fn route_templates() {
+        assert_eq!(route("/emails"), "/emails");
+        assert_eq!(route("/emails/batch"), "/emails/batch");
+        assert_eq!(
+            route("/emails/4ef9a417-02e9-4d39-ad75-9611e0fcc33c"),
+            "/emails/{id}"
+        );
+        assert_eq!(
+            route("/emails/receiving/abc/attachments/def"),
+            "/emails/receiving/{id}/attachments/{id}"
+        );
+        assert_eq!(
+            route("/contacts/steve@example.com/segments/abc"),
+            "/contacts/{id}/segments/{id}"
+        );
+        assert_eq!(route("/broadcasts/abc/send"), "/broadcasts/{id}/send");
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn quota_exceeded() {
+        use crate::{
+            Error,
+            test::{ScriptedTransport, json_response},
+            types::{ErrorKind, RecommendedAction},
+        };
+
+        let transport = ScriptedTransport::new(|_, _| {
+            let body = r#"{"statusCode":429,"message":"","name":"daily_quota_exceeded"}"#;
+            Ok(json_response(429, body))
+        });
+        let config = ConfigBuilder::new("re_test")
+            .transport(transport)
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        let error = resend.emails.get("abc").await.unwrap_err();
+        let Error::Resend { ref response, .. } = error else {
+            panic!("expected a resend error, got {error:?}");
+        };
+        assert_eq!(response.kind(), ErrorKind::DailyQuotaExceeded);
+        assert!(!error.is_retryable());
+        assert_eq!(error.recommended_action(), RecommendedAction::UpgradePlan);
+        assert_eq!(error.status_code(), Some(429));
+    }
+}
@@ -2,9 +2,7 @@ use std::io::Read;
 use std::sync::Arc;
 use std::{fmt, fs::File};
 
-#[cfg(feature = "blocking")]
-use reqwest::blocking::multipart::{Form, Part};
-#[cfg(not(feature = "blocking"))]
+use macro_rules_attribute::apply;
 use reqwest::multipart::{Form, Part};
 
 use reqwest::Method;
@@ -13,7 +11,7 @@ use self::types::UpdateContactResponse;
 use crate::{
     Config, Error, Result,
     contacts::types::ContactPropertyChanges,
-    list_opts::ListOptions,
+    list_opts::{ListOptions, Paginator},
     types::{
         AddContactSegmentResponse, ContactImport, ContactProperty, ContactTopic,
         CreateContactImportOptions, CreateContactImportResponse, CreateContactPropertyOptions,
@@ -30,11 +28,11 @@ use crate::{
 #[derive(Clone)]
 pub struct ContactsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl ContactsSvc {
     /// Create a contact.
     ///
     /// <https://resend.com/docs/api-reference/contacts/create-contact>
-    #[maybe_async::maybe_async]
     pub async fn create(&self, contact: CreateContactOptions) -> Result<ContactId> {
         let path = contact.audience_id.as_ref().map_or_else(
             || "/contacts".to_string(),
@@ -42,8 +40,10 @@ impl ContactsSvc {
         );
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request.json(&contact)).await?;
-        let content = response.json::<types::CreateContactResponse>().await?;
+        let content = self
+            .0
+            .send_json::<types::CreateContactResponse>(request.json(&contact))
+            .await?;
 
         Ok(content.id)
     }
@@ -51,14 +51,12 @@ impl ContactsSvc {
     /// Retrieve a single contact.
     ///
     /// <https://resend.com/docs/api-reference/contacts/get-contact>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, contact_id_or_email: &str) -> Result<Contact> {
         let contact_id_or_email = urlencoding::encode(contact_id_or_email);
         let path = format!("/contacts/{contact_id_or_email}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Contact>().await?;
+        let content = self.0.send_json::<Contact>(request).await?;
 
         Ok(content)
     }
@@ -66,7 +64,6 @@ impl ContactsSvc {
     /// Update an existing contact.
     ///
     /// <https://resend.com/docs/api-reference/contacts/update-contact>
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         contact_id_or_email: &str,
@@ -76,8 +73,10 @@ impl ContactsSvc {
         let path = format!("/contacts/{contact_id_or_email}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateContactResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateContactResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -85,14 +84,15 @@ impl ContactsSvc {
     /// Remove an existing contact.
     ///
     /// <https://resend.com/docs/api-reference/contacts/delete-contact>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, contact_id_or_email: &str) -> Result<bool> {
         let contact_id_or_email = urlencoding::encode(contact_id_or_email);
         let path = format!("/contacts/{contact_id_or_email}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<types::DeleteContactResponse>().await?;
+        let content = self
+            .0
+            .send_json::<types::DeleteContactResponse>(request)
+            .await?;
 
         Ok(content.deleted)
     }
@@ -102,7 +102,6 @@ impl ContactsSvc {
     /// - Default limit: no limit (return everything)
     ///
     /// <https://resend.com/docs/api-reference/contacts/list-contacts>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(
         &self,
         audience: &str,
@@ -111,16 +110,23 @@ impl ContactsSvc {
         let path = format!("/audiences/{audience}/contacts");
 
         let request = self.0.build(Method::GET, &path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Contact>>().await?;
+        let content = self.0.send_json::<ListResponse<Contact>>(request).await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, audience: &str, list_opts: ListOptions<T>) -> Paginator<Contact> {
+        let path = format!("/audiences/{audience}/contacts");
+
+        Paginator::new(Arc::clone(&self.0), path, &list_opts)
+    }
+
     /// Retrieve a list of topics subscriptions for a contact.
     ///
     /// <https://resend.com/docs/api-reference/contacts/get-contact-topic>
-    #[maybe_async::maybe_async]
     pub async fn get_contact_topics<T>(
         &self,
         contact_id_or_email: &str,
@@ -130,16 +136,31 @@ impl ContactsSvc {
         let path = format!("/contacts/{contact_id_or_email}/topics");
 
         let request = self.0.build(Method::GET, &path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<ContactTopic>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<ContactTopic>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::get_contact_topics`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn get_contact_topics_all<T>(
+        &self,
+        contact_id_or_email: &str,
+        list_opts: ListOptions<T>,
+    ) -> Paginator<ContactTopic> {
+        let contact_id_or_email = urlencoding::encode(contact_id_or_email);
+        let path = format!("/contacts/{contact_id_or_email}/topics");
+
+        Paginator::new(Arc::clone(&self.0), path, &list_opts)
+    }
+
     /// Update an existing topic subscription for a contact.
     ///
     /// <https://resend.com/docs/api-reference/contacts/update-contact-topic>
-    #[maybe_async::maybe_async]
     pub async fn update_contact_topics(
         &self,
         contact_id_or_email: &str,
@@ -149,8 +170,10 @@ impl ContactsSvc {
         let path = format!("/contacts/{contact_id_or_email}/topics");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&topics.into())).await?;
-        let content = response.json::<UpdateContactResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateContactResponse>(request.json(&topics.into()))
+            .await?;
 
         Ok(content)
     }
@@ -158,7 +181,6 @@ impl ContactsSvc {
     /// Add an existing contact to a segment.
     ///
     /// <https://resend.com/docs/api-reference/contacts/add-contact-to-segment>
-    #[maybe_async::maybe_async]
     pub async fn add_contact_segment(
         &self,
         contact_id_or_email: &str,
@@ -168,8 +190,10 @@ impl ContactsSvc {
         let path = format!("/contacts/{contact_id_or_email}/segments/{segment_id}");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<AddContactSegmentResponse>().await?;
+        let content = self
+            .0
+            .send_json::<AddContactSegmentResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -177,7 +201,6 @@ impl ContactsSvc {
     /// Remove an existing contact from a segment.
     ///
     /// <https://resend.com/docs/api-reference/contacts/delete-contact-segment>
-    #[maybe_async::maybe_async]
     pub async fn delete_contact_segment(
         &self,
         contact_id_or_email: &str,
@@ -187,8 +210,10 @@ impl ContactsSvc {
         let path = format!("/contacts/{contact_id_or_email}/segments/{segment_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<RemoveContactSegmentResponse>().await?;
+        let content = self
+            .0
+            .send_json::<RemoveContactSegmentResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -196,7 +221,6 @@ impl ContactsSvc {
     /// Retrieve a list of segments that a contact is part of.
     ///
     /// <https://resend.com/docs/api-reference/contacts/list-contact-segments>
-    #[maybe_async::maybe_async]
     pub async fn list_contact_segment<T>(
         &self,
         contact_id_or_email: &str,
@@ -206,16 +230,28 @@ impl ContactsSvc {
         let path = format!("/contacts/{contact_id_or_email}/segments/");
 
         let request = self.0.build(Method::GET, &path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Segment>>().await?;
+        let content = self.0.send_json::<ListResponse<Segment>>(request).await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list_contact_segment`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_contact_segment_all<T>(
+        &self,
+        contact_id_or_email: &str,
+        list_opts: ListOptions<T>,
+    ) -> Paginator<Segment> {
+        let contact_id_or_email = urlencoding::encode(contact_id_or_email);
+        let path = format!("/contacts/{contact_id_or_email}/segments/");
+
+        Paginator::new(Arc::clone(&self.0), path, &list_opts)
+    }
+
     /// Create a custom property for your contacts.
     ///
     /// <https://resend.com/docs/api-reference/contact-properties/create-contact-property>
-    #[maybe_async::maybe_async]
     pub async fn create_property(
         &self,
         contact_property: CreateContactPropertyOptions,
@@ -223,8 +259,10 @@ impl ContactsSvc {
         let path = "/contact-properties";
 
         let request = self.0.build(Method::POST, path);
-        let response = self.0.send(request.json(&contact_property)).await?;
-        let content = response.json::<CreateContactPropertyResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateContactPropertyResponse>(request.json(&contact_property))
+            .await?;
 
         Ok(content)
     }
@@ -232,13 +270,11 @@ impl ContactsSvc {
     /// Retrieve a contact property by its ID.
     ///
     /// <https://resend.com/docs/api-reference/contact-properties/get-contact-property>
-    #[maybe_async::maybe_async]
     pub async fn get_property(&self, contact_property_id: &str) -> Result<ContactProperty> {
         let path = format!("/contact-properties/{contact_property_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ContactProperty>().await?;
+        let content = self.0.send_json::<ContactProperty>(request).await?;
 
         Ok(content)
     }
@@ -246,7 +282,6 @@ impl ContactsSvc {
     /// Update an existing contact property.
     ///
     /// <https://resend.com/docs/api-reference/contact-properties/update-contact-property>
-    #[maybe_async::maybe_async]
     pub async fn update_property(
         &self,
         contact_property_id: &str,
@@ -255,8 +290,10 @@ impl ContactsSvc {
         let path = format!("/contact-properties/{contact_property_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateContactPropertyResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateContactPropertyResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -264,7 +301,6 @@ impl ContactsSvc {
     /// Remove an existing contact property.
     ///
     /// <https://resend.com/docs/api-reference/contact-properties/delete-contact-property>
-    #[maybe_async::maybe_async]
     pub async fn delete_property(
         &self,
         contact_property_id: &str,
@@ -272,8 +308,10 @@ impl ContactsSvc {
         let path = format!("/contact-properties/{contact_property_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DeleteContactPropertyResponse>().await?;
+        let content = self
+            .0
+            .send_json::<DeleteContactPropertyResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -283,7 +321,6 @@ impl ContactsSvc {
     /// - Default limit: 20
     ///
     /// <https://resend.com/docs/api-reference/contact-properties/list-contact-properties>
-    #[maybe_async::maybe_async]
     pub async fn list_properties<T>(
         &self,
         list_opts: ListOptions<T>,
@@ -291,12 +328,25 @@ impl ContactsSvc {
         let path = "/contact-properties";
 
         let request = self.0.build(Method::GET, path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<ContactProperty>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<ContactProperty>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list_properties`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_properties_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<ContactProperty> {
+        Paginator::new(
+            Arc::clone(&self.0),
+            "/contact-properties".to_owned(),
+            &list_opts,
+        )
+    }
+
     /// Create a contact import.
     ///
     /// # Important
@@ -305,22 +355,18 @@ impl ContactsSvc {
     /// using [`std::fs::OpenOptions`] and setting `read` to `true`.
     ///
     /// <https://resend.com/docs/api-reference/contacts/create-contact-import>
-    #[maybe_async::maybe_async]
     pub async fn create_import(
         &self,
-        mut file: File,
+        file: File,
         contact_import: CreateContactImportOptions,
     ) -> Result<CreateContactImportResponse> {
         let path = "/contacts/imports";
 
         let mut contents = String::new();
         #[allow(clippy::verbose_file_reads)]
-        let _size = file
+        let _size = (&file)
             .read_to_string(&mut contents)
-            .map_err(|err| Error::Parse {
-                message: "Error while reading file".to_owned(),
-                source: Some(Box::new(err)),
-            })?;
+            .map_err(|err| Error::parse("Error while reading file", err))?;
 
         let mut form = Form::new().part(
             "file",
@@ -333,16 +379,16 @@ impl ContactsSvc {
         form = Self::add_non_json_part(form, "topics", contact_import.topics.as_ref())?;
 
         if let Some(column_map) = contact_import.column_map {
-            let json = serde_json::to_string(&column_map).map_err(|e| Error::Parse {
-                message: "Could not convert field to JSON".to_owned(),
-                source: Some(Box::new(e)),
-            })?;
+            let json = serde_json::to_string(&column_map)
+                .map_err(|e| Error::parse("Could not convert field to JSON", e))?;
             form = form.part("column_map".to_owned(), Part::text(json));
         }
 
         let request = self.0.build(Method::POST, path).multipart(form);
-        let response = self.0.send(request).await?;
-        let content = response.json::<CreateContactImportResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateContactImportResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -354,10 +400,8 @@ impl ContactsSvc {
     ) -> Result<Form, Error> {
         match value {
             Some(v) => {
-                let val = serde_json::to_value(v).map_err(|e| Error::Parse {
-                    message: "Could not convert field to JSON".to_owned(),
-                    source: Some(Box::new(e)),
-                })?;
+                let val = serde_json::to_value(v)
+                    .map_err(|e| Error::parse("Could not convert field to JSON", e))?;
 
                 let json = match val {
                     serde_json::Value::String(s) => s,
@@ -373,13 +417,11 @@ impl ContactsSvc {
     /// Retrieve a single contact import.
     ///
     /// <https://resend.com/docs/api-reference/contacts/get-contact-import>
-    #[maybe_async::maybe_async]
     pub async fn get_import(&self, contact_import_id: &str) -> Result<ContactImport> {
         let path = format!("/contacts/imports/{contact_import_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ContactImport>().await?;
+        let content = self.0.send_json::<ContactImport>(request).await?;
 
         Ok(content)
     }
@@ -389,7 +431,6 @@ impl ContactsSvc {
     /// - Default limit: 10
     ///
     /// <https://resend.com/docs/api-reference/contacts/list-contact-imports>
-    #[maybe_async::maybe_async]
     pub async fn list_imports<T>(
         &self,
         list_opts: ListOptions<T>,
@@ -397,11 +438,24 @@ impl ContactsSvc {
         let path = "/contacts/imports".to_string();
 
         let request = self.0.build(Method::GET, &path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<ContactImport>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<ContactImport>>(request)
+            .await?;
 
         Ok(content)
     }
+
+    /// Retrieves the items of every page of [`Self::list_imports`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_imports_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<ContactImport> {
+        Paginator::new(
+            Arc::clone(&self.0),
+            "/contacts/imports".to_owned(),
+            &list_opts,
+        )
+    }
 }
 
 impl fmt::Debug for ContactsSvc {
@@ -1002,7 +1056,6 @@ pub mod types {
 mod test {
     use std::collections::HashMap;
 
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -1013,7 +1066,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn no_audience() -> DebugResult<()> {
         let resend = &*CLIENT;
 
@@ -1034,7 +1086,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
         let audience = "test_contacts";
@@ -1129,7 +1180,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn contact_properties() -> DebugResult<()> {
         use crate::types::CreateContactPropertyOptions;
 
@@ -1162,7 +1212,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn segments() -> DebugResult<()> {
         let resend = &*CLIENT;
 
@@ -1209,7 +1258,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn properties() -> DebugResult<()> {
         use crate::{
             contacts::types::ContactPropertyChanges,
@@ -1256,7 +1304,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn contact_import() -> DebugResult<()> {
         use crate::{
             contacts::types::{
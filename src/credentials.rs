@@ -0,0 +1,141 @@
+//! API key providers for rotating keys without rebuilding clients.
+//!
+//! Every request asks the [`CredentialProvider`] of its [`Config`](crate::Config) for the API key
+//! to use, so changing the key a provider returns affects every clone of a [`Resend`] client at
+//! once. [`ApiKeyCell`] is a provider whose key can be swapped at any time, a custom provider can
+//! read the key from a secret store.
+//!
+//! ### Example
+//!
+//! Rotating the API key of a running application:
+//!
+//! ```rust,no_run
+//! # async fn run() -> resend_rs::Result<()> {
+//! use std::{sync::Arc, time::Duration};
+//!
+//! use resend_rs::credentials::ApiKeyCell;
+//! use resend_rs::types::CreateApiKeyOptions;
+//! use resend_rs::{ConfigBuilder, Resend};
+//!
+//! let api_key = Arc::new(ApiKeyCell::new("re_old"));
+//! let config = ConfigBuilder::with_credentials(Arc::clone(&api_key)).build();
+//! let resend = Resend::with_config(config);
+//!
+//! // Later on
+//! let old_id = "b6d24b8e-af0b-4c3c-be0c-359bbd97381e";
+//! let new_key = resend.api_keys.create(CreateApiKeyOptions::new("production")).await?;
+//! let _old_key = api_key.set(new_key.token);
+//!
+//! // Give requests that are still in flight with the old key some time to finish
+//! tokio::time::sleep(Duration::from_secs(60)).await;
+//! resend.api_keys.delete(old_id).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Resend`]: crate::Resend
+
+use std::{
+    fmt,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+/// Supplies the API key for every request.
+///
+/// Called once per request (not per retry), so implementations should be cheap, e.g. by caching
+/// the key and refreshing it in the background.
+pub trait CredentialProvider: fmt::Debug + Send + Sync {
+    /// Returns the API key to use for the next request.
+    fn api_key(&self) -> String;
+}
+
+impl CredentialProvider for String {
+    fn api_key(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T: CredentialProvider + ?Sized> CredentialProvider for Arc<T> {
+    fn api_key(&self) -> String {
+        T::api_key(self)
+    }
+}
+
+/// An API key that can be swapped atomically, shared by every client it has been set on.
+pub struct ApiKeyCell(RwLock<String>);
+
+impl ApiKeyCell {
+    /// Creates a new [`ApiKeyCell`] holding `api_key`.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self(RwLock::new(api_key.into()))
+    }
+
+    /// Replaces the API key, returns the previous one.
+    pub fn set(&self, api_key: impl Into<String>) -> String {
+        let mut current = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *current, api_key.into())
+    }
+
+    /// Returns the current API key.
+    pub fn get(&self) -> String {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl CredentialProvider for ApiKeyCell {
+    fn api_key(&self) -> String {
+        self.get()
+    }
+}
+
+impl fmt::Debug for ApiKeyCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Don't output API key.
+        f.debug_tuple("ApiKeyCell").field(&"re_*********").finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{ConfigBuilder, Resend, credentials::ApiKeyCell};
+
+    #[test]
+    fn rotate() {
+        let api_key = Arc::new(ApiKeyCell::new("re_old"));
+        let config = ConfigBuilder::with_credentials(Arc::clone(&api_key)).build();
+        let resend = Resend::with_config(config);
+        let clone = resend.clone();
+        assert_eq!(resend.api_key(), "re_old");
+
+        assert_eq!(api_key.set("re_new"), "re_old");
+        assert_eq!(resend.api_key(), "re_new");
+        assert_eq!(clone.api_key(), "re_new");
+        assert!(!format!("{api_key:?}").contains("re_new"));
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    #[cfg(feature = "testing")]
+    async fn rotate_in_flight() -> crate::test::DebugResult<()> {
+        use crate::{Error, testing::MockServer, types::ShareEmailOptions};
+
+        let server = MockServer::start();
+        let api_key = Arc::new(ApiKeyCell::new("re_mock_server"));
+        let resend = Resend::with_config(server.config().credentials(Arc::clone(&api_key)).build());
+
+        let email = crate::types::CreateEmailBaseOptions::new("a@a.dev", ["b@b.dev"], "Hi");
+        let id = resend.emails.send(email).await?.id;
+
+        // The mock server only rejects empty keys
+        let _old = api_key.set("");
+        let res = resend.emails.share(&id, ShareEmailOptions::new()).await;
+        assert!(matches!(res, Err(Error::Resend { response, .. }) if response.status_code == 401));
+
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
 use std::fmt;
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 use types::DeleteDomainResponse;
 
 use crate::{Config, Result, domains::types::VerifyDomainResponse};
 use crate::{
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::{CreateDomainClaimOptions, CreateDomainOptions, Domain, DomainChanges, DomainClaim},
 };
 
@@ -16,15 +17,14 @@ use self::types::UpdateDomainResponse;
 #[derive(Clone)]
 pub struct DomainsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl DomainsSvc {
     /// Creates a domain through the Resend Email API.
     ///
     /// <https://resend.com/docs/api-reference/domains/create-domain>
-    #[maybe_async::maybe_async]
     pub async fn create(&self, domain: CreateDomainOptions) -> Result<Domain> {
         let request = self.0.build(Method::POST, "/domains");
-        let response = self.0.send(request.json(&domain)).await?;
-        let content = response.json::<Domain>().await?;
+        let content = self.0.send_json::<Domain>(request.json(&domain)).await?;
 
         Ok(content)
     }
@@ -32,13 +32,11 @@ impl DomainsSvc {
     /// Retrieves a single domain for the authenticated user.
     ///
     /// <https://resend.com/docs/api-reference/domains/get-domain>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, domain_id: &str) -> Result<Domain> {
         let path = format!("/domains/{domain_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Domain>().await?;
+        let content = self.0.send_json::<Domain>(request).await?;
 
         Ok(content)
     }
@@ -46,13 +44,11 @@ impl DomainsSvc {
     /// Verifies an existing domain.
     ///
     /// <https://resend.com/docs/api-reference/domains/verify-domain>
-    #[maybe_async::maybe_async]
     pub async fn verify(&self, domain_id: &str) -> Result<VerifyDomainResponse> {
         let path = format!("/domains/{domain_id}/verify");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<VerifyDomainResponse>().await?;
+        let content = self.0.send_json::<VerifyDomainResponse>(request).await?;
 
         Ok(content)
     }
@@ -60,7 +56,6 @@ impl DomainsSvc {
     /// Updates an existing domain.
     ///
     /// <https://resend.com/docs/api-reference/domains/update-domain>
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         domain_id: &str,
@@ -69,8 +64,10 @@ impl DomainsSvc {
         let path = format!("/domains/{domain_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateDomainResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateDomainResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -80,27 +77,30 @@ impl DomainsSvc {
     /// - Default limit: no limit (return everything)
     ///
     /// <https://resend.com/docs/api-reference/domains/list-domains>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Domain>> {
         let request = self.0.build(Method::GET, "/domains").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Domain>>().await?;
+        let content = self.0.send_json::<ListResponse<Domain>>(request).await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Domain> {
+        Paginator::new(Arc::clone(&self.0), "/domains".to_owned(), &list_opts)
+    }
+
     /// Removes an existing domain.
     ///
     /// Returns whether the domain was deleted successfully.
     ///
     /// <https://resend.com/docs/api-reference/domains/delete-domain>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, domain_id: &str) -> Result<DeleteDomainResponse> {
         let path = format!("/domains/{domain_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DeleteDomainResponse>().await?;
+        let content = self.0.send_json::<DeleteDomainResponse>(request).await?;
 
         Ok(content)
     }
@@ -108,11 +108,12 @@ impl DomainsSvc {
     /// Claim a domain that is already verified by another team.
     ///
     /// <https://resend.com/docs/api-reference/domains/claim-domain>
-    #[maybe_async::maybe_async]
     pub async fn claim(&self, domain_claim: CreateDomainClaimOptions) -> Result<DomainClaim> {
         let request = self.0.build(Method::POST, "/domains/claim");
-        let response = self.0.send(request.json(&domain_claim)).await?;
-        let content = response.json::<DomainClaim>().await?;
+        let content = self
+            .0
+            .send_json::<DomainClaim>(request.json(&domain_claim))
+            .await?;
 
         Ok(content)
     }
@@ -120,13 +121,11 @@ impl DomainsSvc {
     /// Retrieve the latest claim for a domain.
     ///
     /// <https://resend.com/docs/api-reference/domains/get-domain-claim>
-    #[maybe_async::maybe_async]
     pub async fn get_claim(&self, domain_id: &str) -> Result<DomainClaim> {
         let path = format!("/domains/{domain_id}/claim");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DomainClaim>().await?;
+        let content = self.0.send_json::<DomainClaim>(request).await?;
 
         Ok(content)
     }
@@ -134,13 +133,11 @@ impl DomainsSvc {
     /// Trigger DNS verification for a domain claim.
     ///
     /// <https://resend.com/docs/api-reference/domains/verify-domain-claim>
-    #[maybe_async::maybe_async]
     pub async fn verify_claim(&self, domain_id: &str) -> Result<DomainClaim> {
         let path = format!("/domains/{domain_id}/claim/verify");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DomainClaim>().await?;
+        let content = self.0.send_json::<DomainClaim>(request).await?;
 
         Ok(content)
     }
@@ -693,7 +690,6 @@ pub mod types {
 #[cfg(test)]
 #[allow(clippy::needless_return)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         domains::types::DeleteDomainResponse,
         domains::types::{CreateDomainOptions, DomainChanges, Tls},
@@ -703,7 +699,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
 
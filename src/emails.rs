@@ -1,15 +1,17 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 use serde::{Deserialize, Deserializer};
 
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::Attachment,
 };
 use crate::{
     idempotent::Idempotent,
+    meta::WithMeta,
     types::{
         CancelScheduleResponse, CreateEmailBaseOptions, CreateEmailResponse, Email, EmailMetrics,
         GetEmailMetricsOptions, ShareEmailOptions, ShareEmailResponse, UpdateEmailOptions,
@@ -21,39 +23,63 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct EmailsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl EmailsSvc {
     /// Start sending emails through the `Resend` Email API.
     ///
     /// <https://resend.com/docs/api-reference/emails/send-email>
-    #[maybe_async::maybe_async]
     pub async fn send(
         &self,
         email: impl Into<Idempotent<CreateEmailBaseOptions>>,
     ) -> Result<CreateEmailResponse> {
+        Ok(self.send_with_meta(email).await?.data)
+    }
+
+    /// The same as [`EmailsSvc::send`] but also returns the [`ResponseMeta`] of the response.
+    ///
+    /// [`ResponseMeta`]: crate::meta::ResponseMeta
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "resend.emails.send",
+            skip_all,
+            fields(recipients, attachments, attachment_bytes)
+        )
+    )]
+    pub async fn send_with_meta(
+        &self,
+        email: impl Into<Idempotent<CreateEmailBaseOptions>>,
+    ) -> Result<WithMeta<CreateEmailResponse>> {
         let email: Idempotent<CreateEmailBaseOptions> = email.into();
 
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            let _span = span
+                .record("recipients", email.data.recipient_count())
+                .record("attachments", email.data.attachment_count())
+                .record("attachment_bytes", email.data.attachment_bytes());
+        }
+
+        email.data.check_attachments_size()?;
+
         let mut request = self.0.build(Method::POST, "/emails");
 
         if let Some(ref idempotency_key) = email.idempotency_key {
             request = request.header("Idempotency-Key", idempotency_key);
         }
 
-        let response = self.0.send(request.json(&email)).await?;
-        let content = response.json::<CreateEmailResponse>().await?;
-
-        Ok(content)
+        self.0.send_json_with_meta(request.json(&email)).await
     }
 
     /// Retrieve a single email.
     ///
     /// <https://resend.com/docs/api-reference/emails/retrieve-email>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, email_id: &str) -> Result<Email> {
         let path = format!("/emails/{email_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Email>().await?;
+        let content = self.0.send_json::<Email>(request).await?;
 
         Ok(content)
     }
@@ -61,7 +87,6 @@ impl EmailsSvc {
     /// Update a scheduled email.
     ///
     /// <https://resend.com/docs/api-reference/emails/update-email>
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         email_id: &str,
@@ -70,8 +95,10 @@ impl EmailsSvc {
         let path = format!("/emails/{email_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateEmailResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateEmailResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -79,18 +106,15 @@ impl EmailsSvc {
     /// Cancel a scheduled email.
     ///
     /// <https://resend.com/docs/api-reference/emails/cancel-email>
-    #[maybe_async::maybe_async]
     pub async fn cancel(&self, email_id: &str) -> Result<CancelScheduleResponse> {
         let path = format!("/emails/{email_id}/cancel");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<CancelScheduleResponse>().await?;
+        let content = self.0.send_json::<CancelScheduleResponse>(request).await?;
 
         Ok(content)
     }
 
-    #[maybe_async::maybe_async]
     pub async fn share(
         &self,
         email_id: &str,
@@ -99,8 +123,10 @@ impl EmailsSvc {
         let path = format!("/emails/{email_id}/share");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request.json(&options)).await?;
-        let content = response.json::<ShareEmailResponse>().await?;
+        let content = self
+            .0
+            .send_json::<ShareEmailResponse>(request.json(&options))
+            .await?;
 
         Ok(content)
     }
@@ -110,25 +136,28 @@ impl EmailsSvc {
     /// - Default limit: 20
     ///
     /// <https://resend.com/docs/api-reference/emails/list-emails>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Email>> {
         let request = self.0.build(Method::GET, "/emails").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Email>>().await?;
+        let content = self.0.send_json::<ListResponse<Email>>(request).await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Email> {
+        Paginator::new(Arc::clone(&self.0), "/emails".to_owned(), &list_opts)
+    }
+
     /// Retrieve a single attachment from a sent email.
     ///
     /// <https://resend.com/docs/api-reference/attachments/retrieve-sent-email-attachment>
-    #[maybe_async::maybe_async]
     pub async fn get_attachment(&self, email_id: &str, attachment_id: &str) -> Result<Attachment> {
         let path = format!("/emails/{email_id}/attachments/{attachment_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Attachment>().await?;
+        let content = self.0.send_json::<Attachment>(request).await?;
 
         Ok(content)
     }
@@ -136,7 +165,6 @@ impl EmailsSvc {
     /// Retrieve a list of email attachments and their contents.
     ///
     /// <https://resend.com/docs/api-reference/attachments/list-sent-email-attachments>
-    #[maybe_async::maybe_async]
     pub async fn list_attachments<T>(
         &self,
         email_id: &str,
@@ -145,20 +173,33 @@ impl EmailsSvc {
         let path = format!("/emails/{email_id}/attachments");
 
         let request = self.0.build(Method::GET, &path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Attachment>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<Attachment>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list_attachments`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_attachments_all<T>(
+        &self,
+        email_id: &str,
+        list_opts: ListOptions<T>,
+    ) -> Paginator<Attachment> {
+        let path = format!("/emails/{email_id}/attachments");
+
+        Paginator::new(Arc::clone(&self.0), path, &list_opts)
+    }
+
     /// Retrieve email delivery metrics aggregated over a date range.
     ///
     /// <https://resend.com/docs/api-reference/emails/metrics>
-    #[maybe_async::maybe_async]
     pub async fn metrics<T>(&self, options: GetEmailMetricsOptions<T>) -> Result<EmailMetrics> {
         let request = self.0.build(Method::GET, "/emails/metrics").query(&options);
-        let response = self.0.send(request).await?;
-        let content = response.json::<EmailMetrics>().await?;
+        let content = self.0.send_json::<EmailMetrics>(request).await?;
 
         Ok(content)
     }
@@ -166,8 +207,11 @@ impl EmailsSvc {
 
 #[allow(unreachable_pub)]
 pub mod types {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, fs::File, io::Read, path::Path};
 
+    use base64::{display::Base64Display, engine::general_purpose::STANDARD};
+    use bytes::Bytes;
+    use futures_util::{AsyncRead, AsyncReadExt};
     use serde::{Deserialize, Serialize};
 
     use crate::{
@@ -237,6 +281,10 @@ pub mod types {
     }
 
     impl CreateEmailBaseOptions {
+        /// Maximum total size of the attachments of an email once base64 encoded, 40 MB (in
+        /// decimal units, as stated by Resend).
+        pub const MAX_ATTACHMENTS_SIZE: usize = 40_000_000;
+
         /// Creates a new [`CreateEmailBaseOptions`].
         ///
         /// - `from`: Sender email address.
@@ -269,6 +317,56 @@ pub mod types {
             }
         }
 
+        /// Number of `to`, `cc` and `bcc` recipients.
+        #[cfg(feature = "tracing")]
+        pub(crate) fn recipient_count(&self) -> usize {
+            self.to.len()
+                + self.cc.as_ref().map_or(0, Vec::len)
+                + self.bcc.as_ref().map_or(0, Vec::len)
+        }
+
+        /// Number of attachments.
+        #[cfg(feature = "tracing")]
+        pub(crate) fn attachment_count(&self) -> usize {
+            self.attachments.as_ref().map_or(0, Vec::len)
+        }
+
+        /// Total size of the attachments sent by content, in bytes.
+        #[cfg(feature = "tracing")]
+        pub(crate) fn attachment_bytes(&self) -> usize {
+            self.attachments
+                .iter()
+                .flatten()
+                .map(|attachment| match attachment.content_or_path {
+                    ContentOrPath::Content(ref content) => content.len(),
+                    ContentOrPath::Path(_) => 0,
+                })
+                .sum()
+        }
+
+        /// Fails if the attachments sent by content exceed [`Self::MAX_ATTACHMENTS_SIZE`] once
+        /// base64 encoded, instead of uploading them only to be rejected.
+        pub(crate) fn check_attachments_size(&self) -> crate::Result<()> {
+            let size: usize = self
+                .attachments
+                .iter()
+                .flatten()
+                .map(|attachment| match attachment.content_or_path {
+                    ContentOrPath::Content(ref content) => content.len().div_ceil(3) * 4,
+                    ContentOrPath::Path(_) => 0,
+                })
+                .sum();
+
+            if size > Self::MAX_ATTACHMENTS_SIZE {
+                return Err(crate::Error::other(format!(
+                    "attachments are {size} bytes base64 encoded, over the limit of {} bytes per email",
+                    Self::MAX_ATTACHMENTS_SIZE
+                )));
+            }
+
+            Ok(())
+        }
+
         /// Adds or overwrites the HTML version of the message.
         #[inline]
         pub fn with_html(mut self, html: &str) -> Self {
@@ -507,26 +605,111 @@ pub mod types {
     #[must_use]
     #[derive(Debug, Clone, Serialize)]
     pub enum ContentOrPath {
-        /// Content of an attached file.
-        #[serde(rename = "content")]
-        Content(Vec<u8>),
+        /// Content of an attached file, sent base64 encoded.
+        ///
+        /// Cloning it (e.g. with the [`CreateEmailBaseOptions`] in a retry loop) does not copy
+        /// the content.
+        #[serde(rename = "content", serialize_with = "base64_content")]
+        Content(Bytes),
         /// Path where the attachment file is hosted.
         #[serde(rename = "path")]
         Path(String),
     }
 
     impl CreateAttachment {
-        /// Creates a new [`Attachment`] from the content of an attached file.
+        /// Creates a new [`Attachment`] from the content of an attached file, e.g. a `Vec<u8>`,
+        /// a `&'static [u8]` or [`Bytes`].
         #[inline]
-        pub const fn from_content(content: Vec<u8>) -> Self {
+        pub fn from_content(content: impl Into<Bytes>) -> Self {
             Self {
-                content_or_path: ContentOrPath::Content(content),
+                content_or_path: ContentOrPath::Content(content.into()),
                 filename: None,
                 content_type: None,
                 content_id: None,
             }
         }
 
+        /// Reads the local file at `path` into a new [`Attachment`].
+        ///
+        /// The filename is the one of `path` and the content type is inferred from its extension
+        /// or, failing that, from the first bytes of the file. Files that can't fit in an email
+        /// (see [`CreateEmailBaseOptions::MAX_ATTACHMENTS_SIZE`]) are not read.
+        ///
+        /// # Blocking
+        ///
+        /// This opens and reads the file with [`std::fs`], blocking the current thread until the
+        /// whole file is read. In async code, use [`Self::from_file_async`] instead.
+        pub fn from_file(path: impl AsRef<Path>) -> crate::Result<Self> {
+            let path = path.as_ref();
+            let filename = file_name(path)?;
+            let file = File::open(path).map_err(|e| {
+                crate::Error::other(format!("could not open {}: {e}", path.display()))
+            })?;
+
+            Self::from_reader(file, &filename)
+        }
+
+        /// The same as [`Self::from_file`] but reads the file without blocking the executor, on
+        /// a thread pool shared with other async file operations.
+        #[cfg(not(target_arch = "wasm32"))]
+        pub async fn from_file_async(path: impl AsRef<Path>) -> crate::Result<Self> {
+            let path = path.as_ref();
+            let filename = file_name(path)?;
+            let file = async_fs::File::open(path).await.map_err(|e| {
+                crate::Error::other(format!("could not open {}: {e}", path.display()))
+            })?;
+
+            Self::from_async_reader(file, &filename).await
+        }
+
+        /// Reads `reader` to the end into a new [`Attachment`] named `filename`.
+        ///
+        /// The content type is inferred from the extension of `filename` or, failing that, from
+        /// the first bytes read. Fails without reading the rest once the content can't fit in an
+        /// email (see [`CreateEmailBaseOptions::MAX_ATTACHMENTS_SIZE`]).
+        pub fn from_reader(reader: impl Read, filename: &str) -> crate::Result<Self> {
+            let mut content = Vec::new();
+            let _size = reader
+                .take(max_content_len() + 1)
+                .read_to_end(&mut content)
+                .map_err(|e| crate::Error::other(format!("could not read {filename}: {e}")))?;
+
+            Self::from_read_content(content, filename)
+        }
+
+        /// The same as [`Self::from_reader`] but reads an [`AsyncRead`], e.g. a
+        /// `tokio::fs::File` through `tokio_util::compat`.
+        pub async fn from_async_reader(
+            reader: impl AsyncRead + Unpin,
+            filename: &str,
+        ) -> crate::Result<Self> {
+            let mut content = Vec::new();
+            let _size = reader
+                .take(max_content_len() + 1)
+                .read_to_end(&mut content)
+                .await
+                .map_err(|e| crate::Error::other(format!("could not read {filename}: {e}")))?;
+
+            Self::from_read_content(content, filename)
+        }
+
+        fn from_read_content(content: Vec<u8>, filename: &str) -> crate::Result<Self> {
+            if content.len() as u64 > max_content_len() {
+                return Err(crate::Error::other(format!(
+                    "{filename} is over the limit of {} bytes per email once base64 encoded",
+                    CreateEmailBaseOptions::MAX_ATTACHMENTS_SIZE
+                )));
+            }
+
+            let content_type = mime_guess::from_path(filename)
+                .first_raw()
+                .or_else(|| sniff_content_type(&content));
+            let mut attachment = Self::from_content(content).with_filename(filename);
+            attachment.content_type = content_type.map(str::to_owned);
+
+            Ok(attachment)
+        }
+
         /// Creates a new [`Attachment`] from the path where the attachment file is hosted.
         #[inline]
         pub fn from_path(path: &str) -> Self {
@@ -580,10 +763,59 @@ pub mod types {
     impl From<&[u8]> for CreateAttachment {
         #[inline]
         fn from(value: &[u8]) -> Self {
-            value.to_vec().into()
+            Self::from_content(Bytes::copy_from_slice(value))
         }
     }
 
+    impl From<Bytes> for CreateAttachment {
+        #[inline]
+        fn from(value: Bytes) -> Self {
+            Self::from_content(value)
+        }
+    }
+
+    /// Returns the file name of `path`, the default filename of its attachment.
+    fn file_name(path: &Path) -> crate::Result<String> {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| crate::Error::other(format!("{} is not a file", path.display())))
+    }
+
+    /// Largest content that fits in an email once base64 encoded.
+    const fn max_content_len() -> u64 {
+        (CreateEmailBaseOptions::MAX_ATTACHMENTS_SIZE / 4 * 3) as u64
+    }
+
+    /// Infers the content type of common attachments from their magic bytes.
+    fn sniff_content_type(content: &[u8]) -> Option<&'static str> {
+        const SIGNATURES: [(&[u8], &str); 7] = [
+            (b"%PDF-", "application/pdf"),
+            (b"\x89PNG\r\n\x1a\n", "image/png"),
+            (b"\xff\xd8\xff", "image/jpeg"),
+            (b"GIF87a", "image/gif"),
+            (b"GIF89a", "image/gif"),
+            (b"PK\x03\x04", "application/zip"),
+            (b"\x1f\x8b", "application/gzip"),
+        ];
+
+        if content.starts_with(b"RIFF") && content.get(8..12) == Some(b"WEBP") {
+            return Some("image/webp");
+        }
+
+        SIGNATURES
+            .iter()
+            .find(|(signature, _)| content.starts_with(signature))
+            .map(|&(_, content_type)| content_type)
+    }
+
+    /// Serializes `content` as standard base64, without an intermediate `String`.
+    fn base64_content<S: serde::Serializer>(
+        content: &Bytes,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&Base64Display::new(content, &STANDARD))
+    }
+
     /// Received email.
     #[must_use]
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1269,7 +1501,6 @@ where
 #[allow(clippy::unwrap_used)]
 #[allow(clippy::needless_return)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         types::{
@@ -1281,7 +1512,6 @@ mod test {
         test::{CLIENT, DebugResult},
         types::{CreateEmailBaseOptions, Email, ShareEmailResponse, Tag},
     };
-    #[cfg(not(feature = "blocking"))]
     use jiff::{Span, Timestamp, Zoned};
 
     use std::collections::HashMap;
@@ -1310,7 +1540,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let from = "Acme <onboarding@resend.dev>";
         let to = ["delivered@resend.dev"];
@@ -1403,13 +1632,110 @@ mod test {
     }
 
     #[test]
-    #[cfg(feature = "blocking")]
+    fn serialize_attachment_content_as_base64() {
+        use crate::types::CreateAttachment;
+
+        let email = CreateEmailBaseOptions::new("a@a.dev", ["b@b.dev"], "Hi")
+            .with_attachment(
+                CreateAttachment::from_content(&b"%PDF-1.7"[..]).with_filename("a.pdf"),
+            )
+            .with_attachment(CreateAttachment::from_path("https://a.dev/b.pdf"));
+
+        let json = serde_json::to_value(&email).unwrap();
+        assert_eq!(
+            json.get("attachments").unwrap(),
+            &serde_json::json!([
+                { "content": "JVBERi0xLjc=", "filename": "a.pdf" },
+                { "path": "https://a.dev/b.pdf" },
+            ])
+        );
+    }
+
+    #[test]
+    fn attachment_from_file() {
+        use crate::types::CreateAttachment;
+
+        let path = std::env::temp_dir().join("resend-rs-attachment-report.csv");
+        std::fs::write(&path, "id,total\n1,42\n").unwrap();
+        let attachment = CreateAttachment::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            serde_json::to_value(&attachment).unwrap(),
+            serde_json::json!({
+                "content": "aWQsdG90YWwKMSw0Mgo=",
+                "filename": "resend-rs-attachment-report.csv",
+                "contentType": "text/csv",
+            })
+        );
+
+        // Without a known extension, the content type is inferred from the magic bytes
+        let attachment = CreateAttachment::from_reader(&b"%PDF-1.7"[..], "invoice").unwrap();
+        let json = serde_json::to_value(&attachment).unwrap();
+        assert_eq!(json.get("contentType").unwrap(), "application/pdf");
+        let attachment = CreateAttachment::from_reader(&b"plain"[..], "notes").unwrap();
+        let json = serde_json::to_value(&attachment).unwrap();
+        assert_eq!(json.get("contentType"), None);
+
+        assert!(
+            CreateAttachment::from_file(std::env::temp_dir().join("resend-rs-missing")).is_err()
+        );
+    }
+
+    #[test]
+    fn attachments_size_limit() {
+        use crate::types::CreateAttachment;
+
+        let too_large = std::io::repeat(0);
+        let err = CreateAttachment::from_reader(too_large, "zeros.bin").unwrap_err();
+        assert!(err.to_string().contains("zeros.bin"));
+
+        // Each attachment fits, but not both
+        let content = vec![0; CreateEmailBaseOptions::MAX_ATTACHMENTS_SIZE / 4 * 3];
+        let attachment = CreateAttachment::from_reader(content.as_slice(), "zeros.bin").unwrap();
+        let email = CreateEmailBaseOptions::new("a@a.dev", ["b@b.dev"], "Hi")
+            .with_attachment(attachment.clone());
+        assert!(email.check_attachments_size().is_ok());
+        let email = email.with_attachment(attachment);
+        assert!(email.check_attachments_size().is_err());
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn attachment_from_async() {
+        use crate::types::CreateAttachment;
+
+        let reader = futures_util::io::Cursor::new(b"GIF89a".to_vec());
+        let attachment = CreateAttachment::from_async_reader(reader, "logo.png")
+            .await
+            .unwrap();
+        let json = serde_json::to_value(&attachment).unwrap();
+        assert_eq!(json.get("filename").unwrap(), "logo.png");
+        assert_eq!(json.get("contentType").unwrap(), "image/png");
+
+        let path = std::env::temp_dir().join("resend-rs-attachment-logo.gif");
+        std::fs::write(&path, b"GIF89a").unwrap();
+        let attachment = CreateAttachment::from_file_async(&path).await;
+        std::fs::remove_file(&path).unwrap();
+        let json = serde_json::to_value(attachment.unwrap()).unwrap();
+        assert_eq!(
+            json.get("filename").unwrap(),
+            "resend-rs-attachment-logo.gif"
+        );
+        assert_eq!(json.get("contentType").unwrap(), "image/gif");
+        assert!(
+            CreateAttachment::from_file_async(std::env::temp_dir().join("resend-rs-missing"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-client")]
     fn all_blocking() -> DebugResult<()> {
         let from = "Acme <onboarding@resend.dev>";
         let to = ["delivered@resend.dev"];
         let subject = "Hello World!";
 
-        let resend = &*CLIENT;
+        let resend = crate::blocking::Resend::from_async(CLIENT.clone());
         let email = CreateEmailBaseOptions::new(from, to, subject)
             .with_text("Hello World!")
             .with_tag(Tag::new("category", "confirm_email"));
@@ -1423,7 +1749,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn schedule_email() -> DebugResult<()> {
         use crate::emails::types::EmailEvent;
 
@@ -1500,7 +1825,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn share_email() -> DebugResult<()> {
         let from = "Acme <onboarding@resend.dev>";
         let to = ["delivered@resend.dev"];
@@ -1542,7 +1866,7 @@ mod test {
                 ShareEmailOptions::new().with_expires_in("not-a-duration"),
             )
             .await;
-        assert!(matches!(shared, Err(crate::Error::Resend(_))));
+        assert!(matches!(shared, Err(crate::Error::Resend { .. })));
         std::thread::sleep(std::time::Duration::from_secs(1));
 
         let shared = resend
@@ -1552,21 +1876,31 @@ mod test {
                 ShareEmailOptions::new(),
             )
             .await;
-        assert!(matches!(shared, Err(crate::Error::Resend(_))));
+        assert!(matches!(shared, Err(crate::Error::Resend { .. })));
 
         Ok(())
     }
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn list_emails() -> DebugResult<()> {
         let resend = &*CLIENT;
         std::thread::sleep(std::time::Duration::from_secs(1));
 
-        let list_opts = ListOptions::default()
-            .with_limit(3)
-            .list_before("71f170f3-826e-47e3-9128-a5958e3b375e");
+        let emails = (0..5).map(|i| {
+            CreateEmailBaseOptions::new(
+                "Acme <onboarding@resend.dev>",
+                ["delivered@resend.dev"],
+                format!("List {i}"),
+            )
+            .with_text("Hello World!")
+        });
+        let sent = resend.batch.send(emails.collect::<Vec<_>>()).await?;
+        std::thread::sleep(std::time::Duration::from_secs(1));
+
+        // Emails are listed newest first, the first one sent is the oldest.
+        let oldest = &sent.first().expect("should have sent emails").id;
+        let list_opts = ListOptions::default().with_limit(3).list_before(oldest);
 
         let list = resend.emails.list(list_opts).await?;
 
@@ -1580,7 +1914,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn attachments() -> DebugResult<()> {
         let resend = &*CLIENT;
         std::thread::sleep(std::time::Duration::from_secs(1));
@@ -1617,7 +1950,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn template() -> DebugResult<()> {
         use std::collections::HashMap;
 
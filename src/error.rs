@@ -232,6 +232,54 @@ pub mod types {
         ServiceUnavailable,
     }
 
+    impl ErrorKind {
+        /// Returns the [`RecommendedAction`] for this kind of error.
+        #[must_use]
+        pub const fn action(self) -> RecommendedAction {
+            match self {
+                Self::Unrecognized
+                | Self::InvalidIdempotencyKey
+                | Self::ValidationError
+                | Self::NotFound
+                | Self::MethodNotAllowed
+                | Self::InvalidIdempotentRequest
+                | Self::InvalidAttachment
+                | Self::InvalidParameter
+                | Self::MissingRequiredField
+                | Self::MissingRequiredParameter => RecommendedAction::FixRequest,
+                Self::MissingApiKey | Self::RestrictedApiKey | Self::InvalidPermission => {
+                    RecommendedAction::Reauthenticate
+                }
+                Self::EmailAboveQuota | Self::DailyQuotaExceeded | Self::MonthlyQuotaExceeded => {
+                    RecommendedAction::UpgradePlan
+                }
+                Self::SuspendedApiKey => RecommendedAction::ContactSupport,
+                Self::ConcurrentIdempotentRequests
+                | Self::ResourceLocked
+                | Self::RateLimitExceeded
+                | Self::ApplicationError
+                | Self::ServiceUnavailable => RecommendedAction::Retry,
+            }
+        }
+    }
+
+    /// What to do about an error, see [`ErrorKind::action`] and
+    /// [`Error::recommended_action`](crate::Error::recommended_action).
+    #[non_exhaustive]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum RecommendedAction {
+        /// The error is transient, retry the request later.
+        Retry,
+        /// The request is invalid, change it before sending it again.
+        FixRequest,
+        /// The API key is missing or lacks permissions, use a different one.
+        Reauthenticate,
+        /// A quota of the account has been reached, upgrade the plan or wait for it to reset.
+        UpgradePlan,
+        /// The account or API key has been suspended, contact Resend support.
+        ContactSupport,
+    }
+
     impl From<ErrorResponse> for ErrorKind {
         fn from(value: ErrorResponse) -> Self {
             Self::from(value.name)
@@ -270,6 +318,60 @@ pub mod types {
 
 #[cfg(test)]
 mod test {
+    #[test]
+    fn retryable() {
+        use crate::{
+            Error,
+            types::{ErrorKind, ErrorResponse, RecommendedAction},
+        };
+
+        let resend = |status_code, name: &str| {
+            Error::from(ErrorResponse {
+                status_code,
+                message: String::new(),
+                name: name.to_owned(),
+            })
+        };
+
+        assert_eq!(ErrorKind::ResourceLocked.action(), RecommendedAction::Retry);
+        assert_eq!(
+            ErrorKind::MonthlyQuotaExceeded.action(),
+            RecommendedAction::UpgradePlan
+        );
+
+        let error = resend(409, "concurrent_idempotent_requests");
+        assert!(error.is_retryable());
+        assert_eq!(error.status_code(), Some(409));
+
+        let error = resend(401, "missing_api_key");
+        assert!(!error.is_retryable());
+        assert_eq!(
+            error.recommended_action(),
+            RecommendedAction::Reauthenticate
+        );
+
+        let error = resend(503, "something_new");
+        assert!(error.is_retryable());
+        assert_eq!(error.recommended_action(), RecommendedAction::Retry);
+
+        let error = Error::Parse {
+            message: "<html>Bad Gateway</html>".to_owned(),
+            status: Some(502),
+            source: None,
+            context: Box::default(),
+        };
+        assert!(error.is_retryable());
+        assert_eq!(error.status_code(), Some(502));
+
+        let error = Error::other("vetoed");
+        assert!(!error.is_retryable());
+        assert_eq!(error.recommended_action(), RecommendedAction::FixRequest);
+
+        let error = Error::transport("request timed out");
+        assert!(error.is_retryable());
+        assert_eq!(error.status_code(), None);
+    }
+
     /// This test parses [all Resend errors] and makes sure [`crate::types::ErrorKind`] models
     /// them correctly, namely:
     ///
@@ -285,7 +387,6 @@ mod test {
     #[allow(clippy::unwrap_used)]
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn errors_up_to_date() {
         use std::collections::HashSet;
 
@@ -293,6 +394,11 @@ mod test {
 
         use crate::types::{ErrorKind, ErrorResponse};
 
+        // Fetches the docs, so only runs alongside the live API tests.
+        if !crate::test::live() {
+            return;
+        }
+
         let response = reqwest::get("https://resend.com/docs/api-reference/errors")
             .await
             .unwrap();
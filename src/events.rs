@@ -7,12 +7,13 @@
 
 use std::{collections::HashMap, sync::Arc};
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::{
         BroadcastId, CreateEventOptions, CreateEventResponse, DeleteEventResponse, Domain, EmailId,
         GetEventResponse, InboundAttachment, SegmentId, SendEventOptions, SendEventResponse,
@@ -24,15 +25,17 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct EventsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl EventsSvc {
     /// Create a new event that can be used to trigger automations.
     ///
     /// <https://resend.com/docs/api-reference/events/create-event>
-    #[maybe_async::maybe_async]
     pub async fn create(&self, event: CreateEventOptions) -> Result<CreateEventResponse> {
         let request = self.0.build(Method::POST, "/events");
-        let response = self.0.send(request.json(&event)).await?;
-        let content = response.json::<CreateEventResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateEventResponse>(request.json(&event))
+            .await?;
 
         Ok(content)
     }
@@ -40,11 +43,12 @@ impl EventsSvc {
     /// Send a named event to trigger matching automations.
     ///
     /// <https://resend.com/docs/api-reference/events/send-event>
-    #[maybe_async::maybe_async]
     pub async fn send(&self, opts: SendEventOptions) -> Result<SendEventResponse> {
         let request = self.0.build(Method::POST, "/events/send");
-        let response = self.0.send(request.json(&opts)).await?;
-        let content = response.json::<SendEventResponse>().await?;
+        let content = self
+            .0
+            .send_json::<SendEventResponse>(request.json(&opts))
+            .await?;
 
         Ok(content)
     }
@@ -52,13 +56,11 @@ impl EventsSvc {
     /// Retrieve a single event by ID or name.
     ///
     /// <https://resend.com/docs/api-reference/events/get-event>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, event_id: &str) -> Result<GetEventResponse> {
         let path = format!("/events/{event_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<GetEventResponse>().await?;
+        let content = self.0.send_json::<GetEventResponse>(request).await?;
 
         Ok(content)
     }
@@ -66,22 +68,29 @@ impl EventsSvc {
     /// Retrieve a list of events.
     ///
     /// <https://resend.com/docs/api-reference/events/list-events>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(
         &self,
         list_opts: ListOptions<T>,
     ) -> Result<ListResponse<GetEventResponse>> {
         let request = self.0.build(Method::GET, "/events").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<GetEventResponse>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<GetEventResponse>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<GetEventResponse> {
+        Paginator::new(Arc::clone(&self.0), "/events".to_owned(), &list_opts)
+    }
+
     /// Update an existing event schema.
     ///
     /// <https://resend.com/docs/api-reference/events/update-event>
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         event_id: &str,
@@ -90,8 +99,10 @@ impl EventsSvc {
         let path = format!("/events/{event_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateEventResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateEventResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -99,13 +110,11 @@ impl EventsSvc {
     /// Remove an existing event.
     ///
     /// <https://resend.com/docs/api-reference/events/delete-event>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, event_id: &str) -> Result<DeleteEventResponse> {
         let path = format!("/events/{event_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DeleteEventResponse>().await?;
+        let content = self.0.send_json::<DeleteEventResponse>(request).await?;
 
         Ok(content)
     }
@@ -206,10 +215,7 @@ pub mod types {
 ///  let parsed: Result<Event, resend_rs::Error> = try_parse_event(data);
 /// ```
 pub fn try_parse_event(data: &str) -> Result<Event> {
-    serde_json::from_str::<Event>(data).map_err(|e| crate::Error::Parse {
-        message: "Could not parse event".to_owned(),
-        source: Some(Box::new(e)),
-    })
+    serde_json::from_str::<Event>(data).map_err(|e| crate::Error::parse("Could not parse event", e))
 }
 
 /// Parses an event type string into an [`EventType`].
@@ -223,10 +229,8 @@ pub fn try_parse_event(data: &str) -> Result<Event> {
 ///  let parsed: Result<EventType, resend_rs::Error> = try_parse_event_type(data);
 /// ```
 pub fn try_parse_event_type(data: &str) -> Result<EventType> {
-    serde_json::from_str::<EventType>(data).map_err(|e| crate::Error::Parse {
-        message: "Could not parse event type".to_owned(),
-        source: Some(Box::new(e)),
-    })
+    serde_json::from_str::<EventType>(data)
+        .map_err(|e| crate::Error::parse("Could not parse event type", e))
 }
 
 /// Represents any [Resend Event](https://resend.com/docs/dashboard/webhooks/event-types).
@@ -472,14 +476,12 @@ pub enum SuppressionOriginType {
 #[cfg(test)]
 mod test {
     use crate::types::SendEventOptions;
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         events::try_parse_event_type,
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
         types::{CreateContactOptions, CreateEventOptions},
     };
-    #[cfg(not(feature = "blocking"))]
     use strum::EnumCount;
 
     use crate::{
@@ -507,7 +509,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         use crate::types::UpdateEventOptions;
 
@@ -1322,8 +1323,11 @@ mod test {
     #[allow(clippy::unwrap_used)]
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn events_up_to_date() -> DebugResult<()> {
+        if !crate::test::live() {
+            return Ok(());
+        }
+
         let response = reqwest::get("https://resend.com/docs/dashboard/webhooks/event-types")
             .await
             .unwrap();
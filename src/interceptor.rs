@@ -0,0 +1,236 @@
+//! Request/response interceptors run by [`Config::send`].
+//!
+//! Interceptors are registered with [`ConfigBuilder::interceptor`] and form an ordered chain
+//! around every request attempt (including retries):
+//!
+//! - [`Interceptor::on_request`] is called in registration order and can modify the request
+//!   (add headers, rewrite the url, ...), answer it directly with [`Action::Respond`] or veto it
+//!   by returning an error.
+//! - [`Interceptor::on_response`] is called in reverse registration order with the response (or
+//!   error) and can inspect, replace or recover it.
+//!
+//! If an interceptor short-circuits a request, the interceptors after it and the
+//! [`Transport`](crate::transport::Transport) are skipped, but the ones before it still see the
+//! response.
+//!
+//! Requests answered by the [`Cache`](crate::cache::Cache) or not sent because of the
+//! [`Mode`](crate::mode::Mode) never reach the chain, neither method is called for them.
+//!
+//! ### Example
+//!
+//! ```rust
+//! # {
+//! use resend_rs::interceptor::{Action, Interceptor};
+//! use resend_rs::{ConfigBuilder, Error, Resend, Result};
+//!
+//! /// Adds a correlation id to every request.
+//! #[derive(Debug)]
+//! struct CorrelationId(String);
+//!
+//! #[async_trait::async_trait]
+//! impl Interceptor for CorrelationId {
+//!     async fn on_request(&self, mut request: reqwest::Request) -> Result<Action> {
+//!         let value = self.0.parse().expect("valid header value");
+//!         let _old = request.headers_mut().insert("x-correlation-id", value);
+//!         Ok(Action::Continue(request))
+//!     }
+//! }
+//!
+//! /// Refuses to send anything.
+//! #[derive(Debug)]
+//! struct Veto;
+//!
+//! #[async_trait::async_trait]
+//! impl Interceptor for Veto {
+//!     async fn on_request(&self, _request: reqwest::Request) -> Result<Action> {
+//!         Err(Error::other("sending is disabled in this environment"))
+//!     }
+//! }
+//!
+//! let config = ConfigBuilder::new("re_...")
+//!     .interceptor(CorrelationId("7f1c".to_owned()))
+//!     .interceptor(Veto)
+//!     .build();
+//! let resend = Resend::with_config(config);
+//! # }
+//! ```
+//!
+//! [`Config::send`]: crate::Config::send
+
+use std::fmt;
+
+use reqwest::{Request, Response};
+
+use crate::Result;
+
+#[cfg(doc)]
+use crate::ConfigBuilder;
+
+/// What to do with a request after [`Interceptor::on_request`].
+#[derive(Debug)]
+pub enum Action {
+    /// Pass the (possibly modified) request on to the next interceptor.
+    Continue(Request),
+    /// Skip the rest of the chain and answer with this response.
+    Respond(Response),
+}
+
+/// Hook into every request attempt and its response.
+///
+/// Both methods default to passing everything through unchanged.
+///
+/// Async implementations are declared with [`async_trait`](https://docs.rs/async-trait).
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait Interceptor: fmt::Debug + Send + Sync {
+    /// Called with every outgoing request, before it is sent.
+    ///
+    /// Returning an error aborts the request with that error.
+    async fn on_request(&self, request: Request) -> Result<Action> {
+        Ok(Action::Continue(request))
+    }
+
+    /// Called with every response (of any status code) or error.
+    async fn on_response(&self, response: Result<Response>) -> Result<Response> {
+        response
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        ConfigBuilder, Error, Resend, Result,
+        interceptor::{Action, Interceptor},
+        test::{ScriptedTransport, json_response},
+    };
+
+    /// Answers every request with a shared email.
+    fn sharing() -> ScriptedTransport {
+        ScriptedTransport::respond(200, r#"{"id":"abc","url":"https://resend.com/share/abc"}"#)
+    }
+
+    /// Appends its name to the `x-chain` header and to `log`.
+    #[derive(Debug)]
+    struct Named {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        respond: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl Interceptor for Named {
+        async fn on_request(&self, mut request: reqwest::Request) -> Result<Action> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("request {}", self.name));
+
+            if self.respond {
+                let body = r#"{"id":"cached","url":"https://resend.com/share/cached"}"#;
+                return Ok(Action::Respond(json_response(200, body)));
+            }
+
+            let _old = request
+                .headers_mut()
+                .append("x-chain", self.name.parse().unwrap());
+            request.url_mut().set_path("/proxy/emails/abc/share");
+            Ok(Action::Continue(request))
+        }
+
+        async fn on_response(
+            &self,
+            response: Result<reqwest::Response>,
+        ) -> Result<reqwest::Response> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("response {}", self.name));
+            response
+        }
+    }
+
+    #[derive(Debug)]
+    struct Veto;
+
+    #[async_trait::async_trait]
+    impl Interceptor for Veto {
+        async fn on_request(&self, _request: reqwest::Request) -> Result<Action> {
+            Err(Error::other("vetoed"))
+        }
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn interceptor_chain() {
+        let share = crate::types::ShareEmailOptions::new;
+        let named = |name, log: &Arc<Mutex<Vec<String>>>, respond| Named {
+            name,
+            log: Arc::clone(log),
+            respond,
+        };
+
+        // Modify
+        let echo = sharing();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let config = ConfigBuilder::new("re_test")
+            .transport(echo.clone())
+            .interceptor(named("a", &log, false))
+            .interceptor(named("b", &log, false))
+            .build();
+        let shared = Resend::with_config(config)
+            .emails
+            .share("abc", share())
+            .await
+            .unwrap();
+        assert_eq!(shared.id.as_ref(), "abc");
+
+        let request = echo.requests().pop().unwrap();
+        let chain = request
+            .headers()
+            .get_all("x-chain")
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(chain, ["a", "b"]);
+        assert_eq!(request.url().path(), "/proxy/emails/abc/share");
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["request a", "request b", "response b", "response a"]
+        );
+
+        // Short-circuit
+        let echo = sharing();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let config = ConfigBuilder::new("re_test")
+            .transport(echo.clone())
+            .interceptor(named("a", &log, false))
+            .interceptor(named("b", &log, true))
+            .interceptor(named("c", &log, false))
+            .build();
+        let shared = Resend::with_config(config)
+            .emails
+            .share("abc", share())
+            .await
+            .unwrap();
+        assert_eq!(shared.id.as_ref(), "cached");
+        assert_eq!(echo.calls(), 0);
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["request a", "request b", "response b", "response a"]
+        );
+
+        // Veto
+        let echo = sharing();
+        let config = ConfigBuilder::new("re_test")
+            .transport(echo.clone())
+            .interceptor(Veto)
+            .build();
+        let res = Resend::with_config(config)
+            .emails
+            .share("abc", share())
+            .await;
+        assert!(matches!(res, Err(Error::Other { message, .. }) if message == "vetoed"));
+        assert_eq!(echo.calls(), 0);
+    }
+}
@@ -8,12 +8,16 @@
 //! - Firstly *all* requests made by the [`Resend`] client are automatically rate limited to
 //!   9 req/1.1s to avoid collisions with the 10 req/s limit that Resend imposes at the time of
 //!   writing this. Note that this can be changed by changing the `RESEND_RATE_LIMIT` environment
-//!   variable (by default it is set to `9`).
+//!   variable (by default it is set to `9`), or by setting a [`rate_limit::Quota`] with
+//!   [`ConfigBuilder::rate_limit`]. It can also be disabled with
+//!   [`ConfigBuilder::without_rate_limit`]. The limit also adapts to the `ratelimit-*` headers
+//!   of every response, slowing down before the Resend limit is hit.
 //!
 //!   Note that the client can be safely cloned as well as used in async/parallel contexts and the
 //!   rate limit will work as intended. The only exception to this is creating 2 clients via the
 //!   [`Resend::new`] or [`Resend::with_client`] methods which should be avoided, use `.clone()`
-//!   instead.
+//!   instead, or share one limiter between them with [`ConfigBuilder::limiter`]. A custom
+//!   [`rate_limit::Limiter`] can be used to coordinate the rate limit between processes.
 //!
 //! - Secondly, a couple of helper methods as well as macros are implemented in the [`rate_limit`]
 //!   module that allow catching rate limit errors and retrying the request instead of failing.
@@ -25,6 +29,10 @@
 //!   As long as only one program is interacting with the Resend servers on your behalf, this
 //!   module does not need to be used.
 //!
+//!   Alternatively, a [`rate_limit::RetryPolicy`] can be set with
+//!   [`ConfigBuilder::retry_policy`] to automatically retry every request that fails due to rate
+//!   limits, as well as idempotent requests failing due to server errors or connection errors.
+//!
 //! ### Examples
 //!
 //! ```rust,no_run
@@ -51,30 +59,46 @@
 //! ```
 
 pub use client::Resend;
-pub use config::{Config, ConfigBuilder};
+pub use config::{Config, ConfigBuilder, ConfigError};
 pub use serde_json::{Value, json};
 
 mod api_keys;
 mod automations;
 mod batch;
+#[cfg(all(feature = "blocking-client", not(target_arch = "wasm32")))]
+pub mod blocking;
 mod broadcasts;
+pub mod cache;
+pub mod cassette;
+pub mod circuit_breaker;
 mod client;
 mod config;
 mod contacts;
+pub mod credentials;
 mod domains;
 mod emails;
 mod error;
 pub mod events;
 pub mod idempotent;
+pub mod interceptor;
 pub mod list_opts;
 mod logs;
+pub mod meta;
+pub mod mode;
 mod oauth;
+pub mod pool;
+#[cfg(feature = "profiles")]
+mod profile;
 pub mod rate_limit;
 mod receiving;
+pub mod request_opts;
 mod segments;
 mod suppressions;
 mod templates;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 mod topics;
+pub mod transport;
 mod webhooks;
 
 pub mod services {
@@ -87,6 +111,7 @@ pub mod services {
     pub use super::contacts::ContactsSvc;
     pub use super::domains::DomainsSvc;
     pub use super::emails::EmailsSvc;
+    pub use super::events::EventsSvc;
     pub use super::logs::LogsSvc;
     pub use super::oauth::OAuthSvc;
     pub use super::receiving::ReceivingSvc;
@@ -94,6 +119,7 @@ pub mod services {
     pub use super::suppressions::SuppressionsSvc;
     pub use super::templates::TemplateSvc;
     pub use super::topics::TopicsSvc;
+    pub use super::webhooks::WebhookSvc;
 }
 
 pub mod types {
@@ -105,9 +131,10 @@ pub mod types {
     };
     pub use super::automations::types::{
         AddToSegmentStepConfig, Automation, AutomationId, AutomationMinimal, AutomationRun,
-        AutomationRunId, AutomationStatus, AutomationTemplate, Connection, ConnectionType,
-        CreateAutomationOptions, CreateAutomationResponse, DelayStepConfig,
-        DeleteAutomationResponse, DuplicateAutomationResponse, SendEmailStepConfig, Step,
+        AutomationRunId, AutomationRunStatus, AutomationRunsFilter, AutomationStatus,
+        AutomationTemplate, Connection, ConnectionType, CreateAutomationOptions,
+        CreateAutomationResponse, DelayStepConfig, DeleteAutomationResponse,
+        DuplicateAutomationResponse, ListAutomationRunsOptions, SendEmailStepConfig, Step,
         StopAutomationResponse, TriggerStepConfig, UpdateAutomationOptions,
         UpdateAutomationResponse, WaitForEventStepConfig,
     };
@@ -148,7 +175,7 @@ pub mod types {
         MetricsGranularity, ShareEmailOptions, ShareEmailResponse, Tag, UpdateEmailOptions,
         UpdateEmailResponse,
     };
-    pub use super::error::types::{ErrorKind, ErrorResponse};
+    pub use super::error::types::{ErrorKind, ErrorResponse, RecommendedAction};
     pub use super::events::types::{
         ContactIdOrEmail, CreateEventOptions, CreateEventResponse, DeleteEventResponse,
         GetEventResponse, SendEventOptions, SendEventResponse, UpdateEventOptions,
@@ -187,27 +214,45 @@ pub mod types {
 
 /// Error type for operations of a [`Resend`] client.
 ///
+/// Every variant carries an [`ErrorContext`] with the request that failed, see
+/// [`Error::context`]. Use [`Error::is_retryable`] and [`Error::recommended_action`] to decide
+/// how to handle an error.
+///
 /// <https://resend.com/docs/api-reference/errors>
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Errors that may occur during the processing an HTTP request.
-    #[error("http error: {0}")]
-    Http(#[from] reqwest::Error),
+    #[error("http error: {source}")]
+    Http {
+        source: reqwest::Error,
+        context: Box<ErrorContext>,
+    },
 
     /// Errors that may occur during the processing of the API request.
-    #[error("resend error: {0}")]
-    Resend(#[from] types::ErrorResponse),
+    #[error("resend error: {response}")]
+    Resend {
+        #[source]
+        response: types::ErrorResponse,
+        context: Box<ErrorContext>,
+    },
 
     /// Errors that may occur during the parsing of an API response.
     #[error("Failed to parse Resend API response. Received: \n{message}")]
     Parse {
         message: String,
+        /// The HTTP status of the response, if the error occurred while parsing one (e.g. an
+        /// HTML error page of a proxy).
+        status: Option<u16>,
         source: Option<Box<dyn std::error::Error + Send + Sync>>,
+        context: Box<ErrorContext>,
     },
 
     /// Other more generic errors
-    #[error("{0}")]
-    Other(String),
+    #[error("{message}")]
+    Other {
+        message: String,
+        context: Box<ErrorContext>,
+    },
 
     /// Detailed rate limit error. For the old error variant see
     /// [`types::ErrorKind::RateLimitExceeded`].
@@ -216,7 +261,202 @@ pub enum Error {
         ratelimit_limit: Option<u64>,
         ratelimit_remaining: Option<u64>,
         ratelimit_reset: Option<u64>,
+        context: Box<ErrorContext>,
+    },
+
+    /// A transient failure of the [`transport::Transport`] other than the ones of [`Error::Http`],
+    /// e.g. a `tower` timeout or an overloaded `tower` service. Retried like a timeout.
+    #[error("transport error: {source}")]
+    Transport {
+        source: Box<dyn std::error::Error + Send + Sync>,
+        context: Box<ErrorContext>,
     },
+
+    /// The request was not sent because the [`circuit_breaker::CircuitBreaker`] is open.
+    #[error("circuit breaker is open, retry in {retry_after:?}")]
+    CircuitOpen {
+        /// The time until the circuit breaker lets a probe request through.
+        retry_after: std::time::Duration,
+        context: Box<ErrorContext>,
+    },
+}
+
+impl Error {
+    /// Creates a new [`Error::Other`] with an empty [`ErrorContext`].
+    pub fn other(message: impl Into<String>) -> Self {
+        Self::Other {
+            message: message.into(),
+            context: Box::default(),
+        }
+    }
+
+    /// Creates a new [`Error::Transport`] with an empty [`ErrorContext`].
+    pub fn transport(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Transport {
+            source: source.into(),
+            context: Box::default(),
+        }
+    }
+
+    /// Creates a new [`Error::Parse`] with an empty [`ErrorContext`].
+    pub(crate) fn parse(
+        message: impl Into<String>,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self::Parse {
+            message: message.into(),
+            status: None,
+            source: Some(Box::new(source)),
+            context: Box::default(),
+        }
+    }
+
+    /// Returns the [`ErrorContext`] of the request that failed.
+    #[must_use]
+    pub fn context(&self) -> &ErrorContext {
+        match self {
+            Self::Http { context, .. }
+            | Self::Resend { context, .. }
+            | Self::Parse { context, .. }
+            | Self::Other { context, .. }
+            | Self::RateLimit { context, .. }
+            | Self::Transport { context, .. }
+            | Self::CircuitOpen { context, .. } => context,
+        }
+    }
+
+    fn context_mut(&mut self) -> &mut ErrorContext {
+        match self {
+            Self::Http { context, .. }
+            | Self::Resend { context, .. }
+            | Self::Parse { context, .. }
+            | Self::Other { context, .. }
+            | Self::RateLimit { context, .. }
+            | Self::Transport { context, .. }
+            | Self::CircuitOpen { context, .. } => context,
+        }
+    }
+
+    /// Fills in the method and endpoint of the [`ErrorContext`] if they are not set yet.
+    pub(crate) fn with_request(mut self, method: &reqwest::Method, endpoint: &str) -> Self {
+        let context = self.context_mut();
+        if context.method.is_none() {
+            context.method = Some(method.clone());
+        }
+        if context.endpoint.is_none() {
+            context.endpoint = Some(endpoint.to_owned());
+        }
+        self
+    }
+
+    /// Sets the request id of the [`ErrorContext`].
+    pub(crate) fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.context_mut().request_id = request_id;
+        self
+    }
+
+    /// Returns the HTTP status code of the response that caused the error, if any.
+    #[must_use]
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::Http { source, .. } => source.status().map(|status| status.as_u16()),
+            Self::Resend { response, .. } => Some(response.status_code),
+            Self::Parse { status, .. } => *status,
+            Self::Other { .. } | Self::Transport { .. } | Self::CircuitOpen { .. } => None,
+            Self::RateLimit { .. } => Some(429),
+        }
+    }
+
+    /// Returns whether the request may succeed if it is retried later, i.e. the error is
+    /// transient: a timeout, connection error, rate limit, server error or open circuit breaker.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        let status_retryable = |status: u16| status == 429 || status >= 500;
+
+        match self {
+            Self::Http { source, .. } => {
+                source.is_timeout()
+                    || config::is_connect(source)
+                    || self.status_code().is_some_and(status_retryable)
+            }
+            Self::Resend { response, .. } => match response.kind() {
+                types::ErrorKind::Unrecognized => status_retryable(response.status_code),
+                kind => kind.action() == types::RecommendedAction::Retry,
+            },
+            Self::Parse { .. } | Self::Other { .. } => {
+                self.status_code().is_some_and(status_retryable)
+            }
+            Self::Transport { .. } | Self::RateLimit { .. } | Self::CircuitOpen { .. } => true,
+        }
+    }
+
+    /// Returns what should be done about this error.
+    #[must_use]
+    pub fn recommended_action(&self) -> types::RecommendedAction {
+        use types::{ErrorKind, RecommendedAction};
+
+        if self.is_retryable() {
+            return RecommendedAction::Retry;
+        }
+
+        match self {
+            Self::Resend { response, .. } if response.kind() != ErrorKind::Unrecognized => {
+                response.kind().action()
+            }
+            _ => match self.status_code() {
+                Some(401 | 403) => RecommendedAction::Reauthenticate,
+                _ => RecommendedAction::FixRequest,
+            },
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(source: reqwest::Error) -> Self {
+        let context = ErrorContext {
+            endpoint: source.url().map(|url| url.path().to_owned()),
+            ..ErrorContext::default()
+        };
+
+        Self::Http {
+            source,
+            context: Box::new(context),
+        }
+    }
+}
+
+impl From<types::ErrorResponse> for Error {
+    fn from(response: types::ErrorResponse) -> Self {
+        Self::Resend {
+            response,
+            context: Box::default(),
+        }
+    }
+}
+
+/// The request an [`Error`] occurred in.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The HTTP method of the request.
+    pub method: Option<reqwest::Method>,
+    /// The path of the request, e.g. `/emails/4ef9a417-02e9-4d39-ad75-9611e0fcc33c`.
+    pub endpoint: Option<String>,
+    /// The `x-request-id` header of the response, include it in support requests to Resend.
+    pub request_id: Option<String>,
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(ref method) = self.method {
+            write!(f, "{method} ")?;
+        }
+        f.write_str(self.endpoint.as_deref().unwrap_or("<unknown endpoint>"))?;
+        if let Some(ref request_id) = self.request_id {
+            write!(f, " (request id: {request_id})")?;
+        }
+        Ok(())
+    }
 }
 
 macro_rules! define_id_type {
@@ -260,6 +500,104 @@ macro_rules! define_id_type {
 
 pub(crate) use define_id_type;
 
+/// Applied with `#[apply(crate::service_impl!)]` to the `impl` block of a service, implements its
+/// methods and, with the `blocking-client` feature, their blocking versions on the
+/// [`blocking::services`] type of the same name.
+///
+/// Every `pub async fn` gets a blocking version running it to completion and every `pub fn`
+/// returning a [`Paginator`](list_opts::Paginator) one returning a [`blocking::Paginator`]. Other
+/// `pub fn`s fail to compile, so that no method of the async client is missing from the blocking
+/// one. Private methods are skipped.
+// The `@blocking` rules are only used with the `blocking-client` feature.
+#[allow(unused_macro_rules)]
+macro_rules! service_impl {
+    (impl $svc:ident { $($methods:tt)* }) => {
+        impl $svc {
+            $($methods)*
+        }
+
+        #[cfg(all(feature = "blocking-client", not(target_arch = "wasm32")))]
+        // The bounds are the ones of the async methods, which allow the same private bounds.
+        #[allow(private_bounds)]
+        impl $crate::blocking::services::$svc {
+            $crate::service_impl!(@blocking $svc $($methods)*);
+        }
+    };
+
+    (@blocking $svc:ident) => {};
+
+    (@blocking $svc:ident
+        $(#[$attr:meta])*
+        pub async fn $name:ident $(<$($generic:ident $(: $bound:path)?),*>)?
+            (&$self:ident $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty
+            $(where $(
+                $where:ident: $where_trait:ident $(<$($assoc:ident = $assoc_ty:ty),*>)?
+                    $(+ $where_extra:ident)*
+            ),* $(,)?)?
+            $body:block
+        $($rest:tt)*
+    ) => {
+        #[doc = concat!(
+            "Blocking version of [`", stringify!($svc), "::", stringify!($name),
+            "`](crate::services::", stringify!($svc), "::", stringify!($name), ")."
+        )]
+        pub fn $name $(<$($generic $(: $bound)?),*>)? (&$self $(, $arg: $ty)*) -> $ret
+            $(where $(
+                $where: $where_trait $(<$($assoc = $assoc_ty),*>)? $(+ $where_extra)*
+            ),*)?
+        {
+            $self.1.block_on($self.0.$name($($arg),*))
+        }
+
+        $crate::service_impl!(@blocking $svc $($rest)*);
+    };
+
+    (@blocking $svc:ident
+        $(#[$attr:meta])*
+        pub fn $name:ident $(<$($generic:ident $(: $bound:path)?),*>)?
+            (&$self:ident $(, $arg:ident: $ty:ty)* $(,)?) -> Paginator<$item:ident>
+            $body:block
+        $($rest:tt)*
+    ) => {
+        #[doc = concat!(
+            "Blocking version of [`", stringify!($svc), "::", stringify!($name),
+            "`](crate::services::", stringify!($svc), "::", stringify!($name), ")."
+        )]
+        pub fn $name $(<$($generic $(: $bound)?),*>)? (&$self $(, $arg: $ty)*)
+            -> $crate::blocking::Paginator<$item>
+        {
+            $crate::blocking::Paginator::new(
+                std::sync::Arc::clone(&$self.1),
+                $self.0.$name($($arg),*),
+            )
+        }
+
+        $crate::service_impl!(@blocking $svc $($rest)*);
+    };
+
+    (@blocking $svc:ident $(#[$attr:meta])* pub $(async)? fn $name:ident $($rest:tt)*) => {
+        compile_error!(concat!(
+            "`", stringify!($svc), "::", stringify!($name), "` has no blocking version, public ",
+            "service methods must be `async` or return a `Paginator`"
+        ));
+    };
+
+    (@blocking $svc:ident $(#[$attr:meta])* $(pub($($vis:tt)*))? $(async)? fn $($rest:tt)*) => {
+        $crate::service_impl!(@skip $svc $($rest)*);
+    };
+
+    // Skips the signature and body of a private method.
+    (@skip $svc:ident { $($body:tt)* } $($rest:tt)*) => {
+        $crate::service_impl!(@blocking $svc $($rest)*);
+    };
+
+    (@skip $svc:ident $token:tt $($rest:tt)*) => {
+        $crate::service_impl!(@skip $svc $($rest)*);
+    };
+}
+
+pub(crate) use service_impl;
+
 /// Specialized [`Result`] type for an [`Error`].
 ///
 /// [`Result`]: std::result::Result
@@ -267,9 +605,15 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 #[cfg(test)]
 mod test {
-    use std::sync::LazyLock;
+    use std::{
+        sync::{
+            Arc, LazyLock, Mutex, MutexGuard, PoisonError,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
 
-    use crate::{Error, Resend};
+    use crate::{Error, Resend, testing::MockServer, transport::Transport};
 
     #[allow(dead_code, clippy::redundant_pub_crate)]
     pub(crate) struct LocatedError<E: std::error::Error + 'static> {
@@ -303,22 +647,164 @@ mod test {
     #[allow(clippy::redundant_pub_crate)]
     pub(crate) type DebugResult<T, E = LocatedError<Error>> = Result<T, E>;
 
+    #[allow(clippy::redundant_pub_crate)]
+    /// Whether the tests run against the live Resend API (with `RESEND_LIVE_TESTS=1` and
+    /// `RESEND_API_KEY`) instead of a [`MockServer`].
+    pub(crate) fn live() -> bool {
+        std::env::var("RESEND_LIVE_TESTS").is_ok_and(|live| live == "1")
+    }
+
+    /// The server [`CLIENT`] is pointed at unless the tests run against the live API.
+    static SERVER: LazyLock<MockServer> = LazyLock::new(MockServer::start);
+
     #[allow(clippy::redundant_pub_crate)]
     /// Use this client in all tests to ensure rate limits are respected.
     ///
+    /// It is pointed at a shared [`MockServer`], or at the live Resend API if [`live`].
+    ///
     /// Instantiate with:
     /// ```
     /// let resend = &*CLIENT;
     /// ```
-    pub(crate) static CLIENT: LazyLock<Resend> = LazyLock::new(Resend::default);
+    pub(crate) static CLIENT: LazyLock<Resend> = LazyLock::new(|| {
+        if live() {
+            Resend::default()
+        } else {
+            // Without pooling, as a connection would be bound to the runtime of the test that
+            // opened it, which may be gone or blocked by a sleep when another test reuses it.
+            let client = reqwest::Client::builder()
+                .pool_max_idle_per_host(0)
+                .build()
+                .expect("should build the http client");
+            Resend::with_config(SERVER.config().client(client).build())
+        }
+    });
+
+    #[allow(dead_code, clippy::redundant_pub_crate)]
+    /// The body of the `503` error of the Resend API.
+    pub(crate) const UNAVAILABLE: &str =
+        r#"{"statusCode":503,"message":"","name":"service_unavailable"}"#;
+
+    #[allow(clippy::redundant_pub_crate, clippy::unwrap_used)]
+    /// Returns a JSON response with `status` and `body`.
+    pub(crate) fn json_response(status: u16, body: impl Into<String>) -> reqwest::Response {
+        let response = http::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(body.into())
+            .unwrap();
+
+        response.into()
+    }
+
+    type Script =
+        dyn Fn(usize, &reqwest::Request) -> crate::Result<reqwest::Response> + Send + Sync;
+
+    #[allow(dead_code, clippy::redundant_pub_crate)]
+    /// A [`Transport`] answering the `n`th request (from `0`) with `script(n, request)`, and
+    /// keeping every request it received.
+    #[derive(Clone)]
+    pub(crate) struct ScriptedTransport {
+        script: Arc<Script>,
+        requests: Arc<Mutex<Vec<reqwest::Request>>>,
+        delay: Duration,
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    #[allow(dead_code)]
+    impl ScriptedTransport {
+        pub(crate) fn new(
+            script: impl Fn(usize, &reqwest::Request) -> crate::Result<reqwest::Response>
+            + Send
+            + Sync
+            + 'static,
+        ) -> Self {
+            Self {
+                script: Arc::new(script),
+                requests: Arc::default(),
+                delay: Duration::ZERO,
+                in_flight: Arc::default(),
+                max_in_flight: Arc::default(),
+            }
+        }
+
+        /// Answers every request with `status` and the JSON `body`.
+        pub(crate) fn respond(status: u16, body: &'static str) -> Self {
+            Self::new(move |_, _| Ok(json_response(status, body)))
+        }
+
+        /// Answers every request with a `503`.
+        pub(crate) fn unavailable() -> Self {
+            Self::respond(503, UNAVAILABLE)
+        }
+
+        /// Answers every request only after `delay`.
+        pub(crate) const fn with_delay(mut self, delay: Duration) -> Self {
+            self.delay = delay;
+            self
+        }
+
+        /// The requests received so far.
+        pub(crate) fn requests(&self) -> MutexGuard<'_, Vec<reqwest::Request>> {
+            self.requests.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        /// The amount of requests received so far.
+        pub(crate) fn calls(&self) -> usize {
+            self.requests().len()
+        }
+
+        /// The JSON bodies of the requests received so far, [`serde_json::Value::Null`] for
+        /// requests without one.
+        #[allow(clippy::unwrap_used)]
+        pub(crate) fn bodies(&self) -> Vec<serde_json::Value> {
+            self.requests()
+                .iter()
+                .map(|request| {
+                    let body = request.body().and_then(reqwest::Body::as_bytes);
+                    body.map_or(serde_json::Value::Null, |body| {
+                        serde_json::from_slice(body).unwrap()
+                    })
+                })
+                .collect()
+        }
+
+        /// The maximum amount of requests that were in flight at once.
+        pub(crate) fn max_in_flight(&self) -> usize {
+            self.max_in_flight.load(Ordering::SeqCst)
+        }
+    }
+
+    impl std::fmt::Debug for ScriptedTransport {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ScriptedTransport")
+                .field("calls", &self.calls())
+                .finish_non_exhaustive()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for ScriptedTransport {
+        async fn execute(&self, request: reqwest::Request) -> crate::Result<reqwest::Response> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            let _max = self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            if !self.delay.is_zero() {
+                tokio::time::sleep(self.delay).await;
+            }
+            let _in_flight = self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            let mut requests = self.requests();
+            let response = (self.script)(requests.len(), &request);
+            requests.push(request);
+
+            response
+        }
+    }
 
     // <https://stackoverflow.com/a/77859502/12756474>
     #[allow(dead_code, clippy::redundant_pub_crate)]
-    pub(crate) async fn retry<O, E, F>(
-        mut f: F,
-        retries: i32,
-        interval: std::time::Duration,
-    ) -> Result<O, E>
+    pub(crate) async fn retry<O, E, F>(mut f: F, retries: i32, interval: Duration) -> Result<O, E>
     where
         F: AsyncFnMut() -> Result<O, E>,
     {
@@ -1,7 +1,15 @@
-use std::{collections::HashMap, ops::Index};
+use std::{collections::HashMap, fmt, ops::Index, sync::Arc};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use reqwest::Method;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::{Config, Error, Result};
 
 #[derive(Debug, Clone, Copy)]
 pub struct ListBefore {}
@@ -12,6 +20,9 @@ pub struct ListAfter {}
 #[derive(Debug, Clone, Copy)]
 pub struct TimeNotSpecified {}
 
+#[derive(Debug, Clone, Copy)]
+pub struct ListCursor {}
+
 /// Query parameters for retrieving a list of things.
 ///
 /// Note that [`ListOptions::default()`] applies no filters.
@@ -77,6 +88,17 @@ impl<T> ListOptions<T> {
 
         self
     }
+
+    /// Returns the [`Cursor`] at the items requested by these options, with their limit and
+    /// filters.
+    pub fn to_cursor(&self) -> Cursor {
+        Cursor {
+            after: self.after_id.clone(),
+            before: self.before_id.clone(),
+            limit: self.limit,
+            filters: self.other.clone().unwrap_or_default(),
+        }
+    }
 }
 
 impl ListOptions<TimeNotSpecified> {
@@ -103,6 +125,235 @@ impl ListOptions<TimeNotSpecified> {
             other: self.other,
         }
     }
+
+    /// Continues the listing at `cursor`, with its limit and filters (if any) replacing the
+    /// ones set before.
+    pub fn resume(self, cursor: Cursor) -> ListOptions<ListCursor> {
+        let mut other = self.other.unwrap_or_default();
+        other.extend(cursor.filters);
+
+        ListOptions::<ListCursor> {
+            list: std::marker::PhantomData,
+            limit: cursor.limit.or(self.limit),
+            before_id: cursor.before,
+            after_id: cursor.after,
+            other: Some(other).filter(|other| !other.is_empty()),
+        }
+    }
+}
+
+impl From<Cursor> for ListOptions<ListCursor> {
+    fn from(cursor: Cursor) -> Self {
+        ListOptions::default().resume(cursor)
+    }
+}
+
+/// [`ListOptions`] with the typed filters `F` of a list endpoint.
+///
+/// E.g. [`ListAutomationRunsOptions`](crate::types::ListAutomationRunsOptions) for
+/// [`AutomationsSvc::list_runs`](crate::services::AutomationsSvc::list_runs). The filters
+/// available depend on `F`, so filters of another endpoint don't compile. Only the filters
+/// documented in the API reference of an endpoint are typed, the others can still be sent with
+/// [`Self::with_other`]. Plain [`ListOptions`] convert into [`FilteredListOptions`] without
+/// filters.
+///
+/// ## Example
+///
+/// ```
+/// # use resend_rs::types::{AutomationRunStatus, ListAutomationRunsOptions};
+/// let list_opts = ListAutomationRunsOptions::default()
+///   .with_status(AutomationRunStatus::Failed)
+///   .with_limit(10)
+///   .list_after("71f170f3-826e-47e3-9128-a5958e3b375e");
+/// ```
+#[must_use]
+#[derive(Debug, Clone, Serialize)]
+// The `List` marker never reaches the wire, see `ListRecipientsOptions`.
+#[serde(bound(serialize = "F: Serialize"))]
+pub struct FilteredListOptions<F, List = TimeNotSpecified> {
+    #[serde(flatten)]
+    pub(crate) filter: F,
+
+    #[serde(flatten)]
+    pagination: ListOptions<List>,
+}
+
+impl<F: Default> Default for FilteredListOptions<F> {
+    /// Applies no filters.
+    fn default() -> Self {
+        Self {
+            filter: F::default(),
+            pagination: ListOptions::default(),
+        }
+    }
+}
+
+impl<F: Default, List> From<ListOptions<List>> for FilteredListOptions<F, List> {
+    fn from(pagination: ListOptions<List>) -> Self {
+        Self {
+            filter: F::default(),
+            pagination,
+        }
+    }
+}
+
+impl<F, List> FilteredListOptions<F, List> {
+    /// Number of things to retrieve, see [`ListOptions::with_limit`].
+    #[inline]
+    pub fn with_limit(mut self, limit: u8) -> Self {
+        self.pagination = self.pagination.with_limit(limit);
+        self
+    }
+
+    /// Use this to add any arbitrary key value pairs.
+    pub fn with_other(mut self, key: &str, value: Value) -> Self {
+        self.pagination = self.pagination.with_other(key, value);
+        self
+    }
+
+    /// Returns the [`Cursor`] at the items requested by these options, with their limit and
+    /// filters, see [`ListOptions::to_cursor`].
+    pub fn to_cursor(&self) -> Cursor
+    where
+        F: Serialize,
+    {
+        let mut cursor = self.pagination.to_cursor();
+        if let Ok(Value::Object(filter)) = serde_json::to_value(&self.filter) {
+            cursor.filters.extend(filter);
+        }
+
+        cursor
+    }
+}
+
+impl<F> FilteredListOptions<F, TimeNotSpecified> {
+    /// The id before which we'll retrieve the items. This id will *not* be included in the list.
+    #[inline]
+    pub fn list_before(self, id: &str) -> FilteredListOptions<F, ListBefore> {
+        FilteredListOptions {
+            filter: self.filter,
+            pagination: self.pagination.list_before(id),
+        }
+    }
+
+    /// The id after which we'll retrieve the items. This id will *not* be included in the list.
+    #[inline]
+    pub fn list_after(self, id: &str) -> FilteredListOptions<F, ListAfter> {
+        FilteredListOptions {
+            filter: self.filter,
+            pagination: self.pagination.list_after(id),
+        }
+    }
+
+    /// Continues the listing at `cursor`, see [`ListOptions::resume`]. The filters set here
+    /// replace the ones of the cursor.
+    pub fn resume(self, mut cursor: Cursor) -> FilteredListOptions<F, ListCursor>
+    where
+        F: Serialize,
+    {
+        if let Ok(Value::Object(filter)) = serde_json::to_value(&self.filter) {
+            cursor.filters.retain(|key, _| !filter.contains_key(key));
+        }
+
+        FilteredListOptions {
+            filter: self.filter,
+            pagination: self.pagination.resume(cursor),
+        }
+    }
+}
+
+/// A serializable position in a list, to checkpoint a long listing and resume it later, e.g.
+/// after a restart.
+///
+/// A cursor is captured with [`ListResponse::next_cursor`], [`Paginator::cursor`] or
+/// [`ListOptions::to_cursor`] and turned back into [`ListOptions`] with [`ListOptions::resume`]
+/// (or [`From`]).
+///
+/// ```rust,no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures_util::StreamExt;
+/// use resend_rs::{Resend, list_opts::{Cursor, ListOptions}};
+///
+/// let resend = Resend::default();
+/// let checkpoint = std::fs::read_to_string("export.cursor").ok();
+///
+/// let cursor = match checkpoint {
+///     Some(checkpoint) => serde_json::from_str::<Cursor>(&checkpoint)?,
+///     None => Cursor::default(),
+/// };
+///
+/// let mut logs = resend.logs.list_all(ListOptions::default().with_limit(100).resume(cursor));
+/// while let Some(log) = logs.next().await {
+///     println!("{}", log?.id);
+///     if let Some(cursor) = logs.cursor() {
+///         std::fs::write("export.cursor", serde_json::to_string(&cursor)?)?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    before: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    limit: Option<u8>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub(crate) filters: HashMap<String, Value>,
+}
+
+impl Cursor {
+    /// Creates a new [`Cursor`] at the items after `id`, see [`ListOptions::list_after`].
+    pub fn after(id: &str) -> Self {
+        Self {
+            after: Some(id.to_owned()),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a new [`Cursor`] at the items before `id`, see [`ListOptions::list_before`].
+    pub fn before(id: &str) -> Self {
+        Self {
+            before: Some(id.to_owned()),
+            ..Self::default()
+        }
+    }
+
+    /// The id after which the listing continues, if any.
+    #[must_use]
+    pub fn after_id(&self) -> Option<&str> {
+        self.after.as_deref()
+    }
+
+    /// The id before which the listing continues, if any.
+    #[must_use]
+    pub fn before_id(&self) -> Option<&str> {
+        self.before.as_deref()
+    }
+
+    /// Creates a new [`Cursor`] from the query of a page.
+    fn from_query(mut query: Map<String, Value>) -> Self {
+        let mut id = |key: &str| match query.remove(key) {
+            Some(Value::String(id)) => Some(id),
+            _ => None,
+        };
+        let after = id("after");
+        let before = id("before");
+        let limit = query
+            .remove("limit")
+            .and_then(|limit| limit.as_u64())
+            .and_then(|limit| u8::try_from(limit).ok());
+
+        Self {
+            after,
+            before,
+            limit,
+            filters: query.into_iter().collect(),
+        }
+    }
 }
 
 /// Paginated response.
@@ -137,3 +388,481 @@ impl<T> ListResponse<T> {
         self.data.len()
     }
 }
+
+impl<T: ListItem> ListResponse<T> {
+    /// Returns the [`Cursor`] at the items after this page, `None` if there are none.
+    ///
+    /// The cursor carries no limit or filters, see [`ListOptions::resume`].
+    pub fn next_cursor(&self) -> Option<Cursor> {
+        self.data
+            .last()
+            .filter(|_| self.has_more)
+            .map(|last| Cursor::after(last.list_id()))
+    }
+}
+
+/// An item of a [`ListResponse`], identified by the id used to paginate.
+pub trait ListItem {
+    /// The id of the item, as passed to [`ListOptions::list_after`] and
+    /// [`ListOptions::list_before`].
+    fn list_id(&self) -> &str;
+}
+
+macro_rules! list_item {
+    ($($item:ident),* $(,)?) => {
+        $(
+            impl ListItem for crate::types::$item {
+                #[inline]
+                fn list_id(&self) -> &str {
+                    self.id.as_ref()
+                }
+            }
+        )*
+    };
+}
+
+list_item!(
+    ApiKey,
+    Attachment,
+    AutomationMinimal,
+    Broadcast,
+    BroadcastClickedLink,
+    BroadcastRecipient,
+    Contact,
+    ContactImport,
+    ContactProperty,
+    ContactTopic,
+    Domain,
+    Email,
+    GetEventResponse,
+    InboundEmail,
+    Log,
+    OAuthGrant,
+    Segment,
+    Suppression,
+    Template,
+    Topic,
+    Webhook,
+);
+
+#[cfg(not(target_arch = "wasm32"))]
+type PageFuture<I> = Pin<Box<dyn Future<Output = Result<Page<I>>> + Send>>;
+#[cfg(target_arch = "wasm32")]
+type PageFuture<I> = Pin<Box<dyn Future<Output = Result<Page<I>>>>>;
+
+/// The items of a page and the query of the next one, if any.
+type Page<I> = (Vec<I>, Option<Map<String, Value>>);
+
+/// Every item of a list endpoint, returned by the `*_all` variants of the `list` methods (e.g.
+/// [`EmailsSvc::list_all`](crate::services::EmailsSvc::list_all)).
+///
+/// This is a [`Stream`](futures_core::Stream) (see `blocking::Paginator` for an [`Iterator`]) of
+/// the items of every page, starting with the page requested by the [`ListOptions`]. Pages are
+/// only fetched once their items are polled, each through the rate limit and retries of the
+/// client, so e.g. `take(n)` fetches no more pages than needed. Options set with
+/// [`ListOptions::list_before`] paginate backwards.
+///
+/// The stream ends after the first error, [`Paginator::cursor`] returns where to resume it.
+///
+/// ```rust,no_run
+/// # async fn run() -> resend_rs::Result<()> {
+/// use futures_util::TryStreamExt;
+/// use resend_rs::{Resend, list_opts::ListOptions};
+///
+/// let resend = Resend::default();
+///
+/// let mut emails = resend.emails.list_all(ListOptions::default().with_limit(100));
+/// while let Some(email) = emails.try_next().await? {
+///     println!("{}", email.id);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "streams do nothing unless polled"]
+pub struct Paginator<I> {
+    config: Arc<Config>,
+    path: String,
+    /// Query of the next page, `None` once there is none.
+    query: Option<Map<String, Value>>,
+    /// Query of the page of `items`.
+    current: Option<Map<String, Value>>,
+    /// Id of the last item returned from `items`.
+    last: Option<String>,
+    items: std::vec::IntoIter<I>,
+    error: Option<Error>,
+    failed: bool,
+    pending: Option<PageFuture<I>>,
+}
+
+impl<I> fmt::Debug for Paginator<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Paginator")
+            .field("path", &self.path)
+            .field("query", &self.query)
+            .field("buffered", &self.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I> Paginator<I> {
+    /// Returns the [`Cursor`] at the next item, including the limit and filters of the
+    /// [`ListOptions`], `None` once every item was returned.
+    ///
+    /// When paginating backwards, the cursor is at the start of the current page, so resuming
+    /// returns the items of the current page already returned again.
+    pub fn cursor(&self) -> Option<Cursor> {
+        if self.items.len() == 0 {
+            return self.query.clone().map(Cursor::from_query);
+        }
+
+        let mut query = self.current.clone()?;
+        if let Some(ref last) = self.last
+            && !query.contains_key("before")
+        {
+            let _old = query.insert("after".to_owned(), Value::from(last.as_str()));
+        }
+
+        Some(Cursor::from_query(query))
+    }
+}
+
+impl<I> Paginator<I>
+where
+    I: ListItem + DeserializeOwned + Send + 'static,
+{
+    /// Creates a new [`Paginator`] over the `GET` endpoint `path`, starting with the page
+    /// requested by `query`.
+    pub(crate) fn new(config: Arc<Config>, path: String, query: &impl Serialize) -> Self {
+        let (query, error) = match serde_json::to_value(query) {
+            Ok(Value::Object(mut query)) => {
+                query.retain(|_, value| !value.is_null());
+                (Some(query), None)
+            }
+            Ok(_) => (None, Some(Error::other("list options should be a map"))),
+            Err(e) => (None, Some(Error::parse("invalid list options", e))),
+        };
+
+        Self {
+            config,
+            path,
+            query,
+            current: None,
+            last: None,
+            items: Vec::new().into_iter(),
+            failed: error.is_some(),
+            error,
+            pending: None,
+        }
+    }
+
+    /// Fetches the page requested by `query`.
+    async fn fetch(
+        config: Arc<Config>,
+        path: String,
+        mut query: Map<String, Value>,
+    ) -> Result<Page<I>> {
+        let request = config.build(Method::GET, &path).query(&query);
+        let page = config.send_json::<ListResponse<I>>(request).await?;
+
+        let next = match (page.has_more, page.data.first(), page.data.last()) {
+            (true, Some(first), Some(last)) => {
+                let (key, id) = if query.contains_key("before") {
+                    ("before", first.list_id())
+                } else {
+                    ("after", last.list_id())
+                };
+                let _old = query.insert(key.to_owned(), Value::from(id));
+                Some(query)
+            }
+            _ => None,
+        };
+
+        Ok((page.data, next))
+    }
+
+    /// Returns the next buffered item, if any.
+    fn next_item(&mut self) -> Option<I> {
+        let item = self.items.next()?;
+        self.last = Some(item.list_id().to_owned());
+        Some(item)
+    }
+
+    /// Buffers the fetched `page`, or keeps its error.
+    fn fetched(&mut self, page: Result<Page<I>>) {
+        match page {
+            Ok((items, next)) => {
+                self.items = items.into_iter();
+                self.current = std::mem::replace(&mut self.query, next);
+                self.last = None;
+            }
+            Err(error) => {
+                self.error = Some(error);
+                self.failed = true;
+            }
+        }
+    }
+}
+
+impl<I> futures_core::Stream for Paginator<I>
+where
+    I: ListItem + DeserializeOwned + Send + Unpin + 'static,
+{
+    type Item = Result<I>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(error) = this.error.take() {
+                return Poll::Ready(Some(Err(error)));
+            }
+            if let Some(item) = this.next_item() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.failed {
+                return Poll::Ready(None);
+            }
+
+            let mut pending = if let Some(pending) = this.pending.take() {
+                pending
+            } else {
+                let Some(query) = this.query.clone() else {
+                    return Poll::Ready(None);
+                };
+                Box::pin(Self::fetch(
+                    Arc::clone(&this.config),
+                    this.path.clone(),
+                    query,
+                ))
+            };
+
+            let Poll::Ready(page) = pending.as_mut().poll(cx) else {
+                this.pending = Some(pending);
+                return Poll::Pending;
+            };
+            this.fetched(page);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use crate::test::{ScriptedTransport, json_response};
+
+    /// Responds with pages of two segments, or with a `500` past `fail_after` requests.
+    fn paged(fail_after: Option<usize>) -> ScriptedTransport {
+        const IDS: [&str; 5] = ["e5", "e4", "e3", "e2", "e1"];
+
+        ScriptedTransport::new(move |call, request| {
+            if fail_after.is_some_and(|n| call >= n) {
+                let body = r#"{"statusCode":500,"message":"","name":"internal_server_error"}"#;
+                return Ok(json_response(500, body));
+            }
+
+            let query = request.url().query().unwrap_or_default();
+            let position = |id: &str| IDS.iter().position(|&i| i == id).unwrap();
+            let param = |key: &str| {
+                let prefix = format!("{key}=");
+                query
+                    .split('&')
+                    .find_map(|pair| pair.strip_prefix(prefix.as_str()))
+            };
+            let (start, end) = match (param("after"), param("before")) {
+                (Some(after), _) => (position(after) + 1, IDS.len().min(position(after) + 3)),
+                (None, Some(before)) => (position(before).saturating_sub(2), position(before)),
+                (None, None) => (0, 2),
+            };
+            let has_more = if param("before").is_some() {
+                start > 0
+            } else {
+                end < IDS.len()
+            };
+            let data: Vec<_> = IDS
+                .get(start..end)
+                .unwrap()
+                .iter()
+                .map(|id| serde_json::json!({ "id": id, "name": "Newsletter", "created_at": "" }))
+                .collect();
+            let body = serde_json::json!({ "has_more": has_more, "data": data });
+
+            Ok(json_response(200, body.to_string()))
+        })
+    }
+
+    /// Takes the queries of the requests `pages` received so far.
+    fn queries(pages: &ScriptedTransport) -> Vec<String> {
+        let requests = std::mem::take(&mut *pages.requests());
+        requests
+            .iter()
+            .map(|request| request.url().query().unwrap_or_default().to_owned())
+            .collect()
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn paginator() {
+        use futures_util::{StreamExt, TryStreamExt};
+
+        use super::ListOptions;
+        use crate::{ConfigBuilder, Error, Resend};
+
+        let pages = paged(None);
+        let config = ConfigBuilder::new("re_test")
+            .transport(pages.clone())
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+        let ids = |segments: Vec<crate::types::Segment>| -> Vec<String> {
+            segments.into_iter().map(|s| s.id.to_string()).collect()
+        };
+
+        let all = resend
+            .segments
+            .list_all(ListOptions::default().with_limit(2))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids(all), ["e5", "e4", "e3", "e2", "e1"]);
+        let requests = queries(&pages);
+        assert_eq!(
+            requests,
+            ["limit=2", "after=e4&limit=2", "after=e2&limit=2"]
+        );
+
+        // Pages are only fetched when needed
+        let first: Vec<_> = resend
+            .segments
+            .list_all(ListOptions::default())
+            .take(3)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids(first), ["e5", "e4", "e3"]);
+        assert_eq!(queries(&pages).len(), 2);
+
+        // Backwards
+        let newer = resend
+            .segments
+            .list_all(ListOptions::default().list_before("e1"))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids(newer), ["e3", "e2", "e5", "e4"]);
+        let requests = queries(&pages);
+        assert_eq!(requests, ["before=e1", "before=e3"]);
+
+        // The stream ends after the first error
+        let failing = paged(Some(1));
+        let config = ConfigBuilder::new("re_test")
+            .transport(failing.clone())
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+        let items: Vec<_> = resend
+            .segments
+            .list_all(ListOptions::default())
+            .collect()
+            .await;
+        assert!(matches!(
+            items.as_slice(),
+            [Ok(_), Ok(_), Err(Error::Resend { .. })]
+        ));
+        assert_eq!(failing.calls(), 2);
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn cursor() {
+        use futures_util::{StreamExt, TryStreamExt};
+
+        use super::{Cursor, ListOptions, ListResponse};
+        use crate::{ConfigBuilder, Resend, types::Segment};
+
+        let pages = paged(None);
+        let config = ConfigBuilder::new("re_test")
+            .transport(pages.clone())
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        let list_opts = ListOptions::default()
+            .with_limit(2)
+            .with_other("name", "Newsletter".into());
+        let mut segments = resend.segments.list_all(list_opts);
+        let start = serde_json::to_value(segments.cursor().unwrap()).unwrap();
+        assert_eq!(
+            start,
+            serde_json::json!({ "limit": 2, "filters": { "name": "Newsletter" } })
+        );
+        for _ in 0..3 {
+            let _segment = segments.next().await.unwrap().unwrap();
+        }
+
+        // Persisted and resumed mid-page
+        let cursor = serde_json::to_string(&segments.cursor().unwrap()).unwrap();
+        assert_eq!(
+            cursor,
+            r#"{"after":"e3","limit":2,"filters":{"name":"Newsletter"}}"#
+        );
+        let cursor: Cursor = serde_json::from_str(&cursor).unwrap();
+        let rest: Vec<Segment> = resend
+            .segments
+            .list_all(cursor.into())
+            .try_collect()
+            .await
+            .unwrap();
+        let ids: Vec<_> = rest.iter().map(|segment| segment.id.as_ref()).collect();
+        assert_eq!(ids, ["e2", "e1"]);
+        let requests = queries(&pages);
+        assert_eq!(requests.last().unwrap(), "after=e3&limit=2&name=Newsletter");
+
+        // Every item was returned
+        let _rest: Vec<_> = segments.by_ref().collect().await;
+        assert_eq!(segments.cursor(), None);
+
+        let page: ListResponse<Segment> = serde_json::from_value(serde_json::json!({
+            "has_more": true,
+            "data": [{ "id": "e5", "name": "", "created_at": "" }],
+        }))
+        .unwrap();
+        assert_eq!(page.next_cursor(), Some(Cursor::after("e5")));
+        let list_opts = ListOptions::default().with_limit(3).list_before("e1");
+        assert_eq!(list_opts.to_cursor().before_id(), Some("e1"));
+        assert_eq!(
+            ListOptions::from(list_opts.to_cursor()).to_cursor(),
+            list_opts.to_cursor()
+        );
+    }
+
+    /// Returns the query `list_opts` is sent with.
+    fn query<T: serde::Serialize>(list_opts: &T) -> String {
+        let request = reqwest::Client::new()
+            .get("https://api.resend.com/automations/a1/runs")
+            .query(list_opts)
+            .build()
+            .unwrap();
+
+        request.url().query().unwrap_or_default().to_owned()
+    }
+
+    #[test]
+    fn filtered() {
+        use super::ListOptions;
+        use crate::types::{AutomationRunStatus, ListAutomationRunsOptions};
+
+        let list_opts = ListAutomationRunsOptions::from(ListOptions::default().with_limit(5))
+            .with_status(AutomationRunStatus::Failed)
+            .list_after("r1");
+        assert_eq!(query(&list_opts), "status=failed&limit=5&after=r1");
+
+        // The filters replace the ones of the cursor
+        let cursor = ListAutomationRunsOptions::default()
+            .with_status(AutomationRunStatus::Running)
+            .with_other("foo", "bar".into())
+            .list_after("r3")
+            .to_cursor();
+        let list_opts = ListAutomationRunsOptions::default()
+            .with_status(AutomationRunStatus::Completed)
+            .resume(cursor);
+        assert_eq!(query(&list_opts), "status=completed&after=r3&foo=bar");
+    }
+}
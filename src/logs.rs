@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::Log,
 };
 
@@ -12,17 +13,16 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct LogsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl LogsSvc {
     /// Retrieve a single API request log.
     ///
     /// <https://resend.com/docs/api-reference/logs/retrieve-log>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, log_id: &str) -> Result<Log> {
         let path = format!("/logs/{log_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Log>().await?;
+        let content = self.0.send_json::<Log>(request).await?;
 
         Ok(content)
     }
@@ -32,14 +32,19 @@ impl LogsSvc {
     /// - Default limit: 20
     ///
     /// <https://resend.com/docs/api-reference/logs/list-logs>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Log>> {
         let request = self.0.build(Method::GET, "/logs").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Log>>().await?;
+        let content = self.0.send_json::<ListResponse<Log>>(request).await?;
 
         Ok(content)
     }
+
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Log> {
+        Paginator::new(Arc::clone(&self.0), "/logs".to_owned(), &list_opts)
+    }
 }
 
 #[allow(unreachable_pub)]
@@ -69,7 +74,6 @@ pub mod types {
 #[cfg(test)]
 #[allow(clippy::needless_return, clippy::unwrap_used)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -77,7 +81,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
 
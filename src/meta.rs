@@ -0,0 +1,193 @@
+//! Response metadata returned by the `*_with_meta` methods.
+//!
+//! Most methods only return the deserialized body of the response. The `*_with_meta` variants
+//! (e.g. [`EmailsSvc::send_with_meta`]) additionally return the status, request id, rate limit and
+//! idempotency headers of the response in a [`WithMeta`].
+//!
+//! ### Example
+//!
+//! ```rust,no_run
+//! # async fn run() -> resend_rs::Result<()> {
+//! use resend_rs::{Resend, types::CreateEmailBaseOptions};
+//!
+//! let resend = Resend::default();
+//! let email = CreateEmailBaseOptions::new("Acme <onboarding@a.dev>", ["delivered@resend.dev"], "Hi")
+//!     .with_text("Hello World!");
+//!
+//! let sent = resend.emails.send_with_meta(email).await?;
+//! println!("id: {}, request id: {:?}", sent.id, sent.meta.request_id);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`EmailsSvc::send_with_meta`]: crate::services::EmailsSvc::send_with_meta
+
+use std::ops::{Deref, DerefMut};
+
+use reqwest::Response;
+use reqwest::{
+    StatusCode,
+    header::{HeaderMap, HeaderName},
+};
+use serde::de::DeserializeOwned;
+
+use crate::{Error, Result, rate_limit::RateLimitHeaders};
+
+/// A response body of type `T` along with the [`ResponseMeta`] of its response.
+///
+/// Dereferences to `T`.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct WithMeta<T> {
+    /// The deserialized response body.
+    pub data: T,
+    /// The metadata of the response.
+    pub meta: ResponseMeta,
+}
+
+impl<T> WithMeta<T> {
+    /// Returns the response body, discarding the metadata.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+
+    /// Maps the response body with `f`, keeping the metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> WithMeta<U> {
+        WithMeta {
+            data: f(self.data),
+            meta: self.meta,
+        }
+    }
+
+    /// Deserializes the body of `response` and collects its metadata.
+    pub(crate) async fn from_response(response: Response) -> Result<Self>
+    where
+        T: DeserializeOwned,
+    {
+        let meta = ResponseMeta::new(response.status(), response.headers().clone());
+        let body = response
+            .bytes()
+            .await
+            .map_err(|error| Error::from(error).with_request_id(meta.request_id.clone()))?;
+        let data = serde_json::from_slice::<T>(&body).map_err(|source| {
+            Error::Parse {
+                message: String::from_utf8_lossy(&body).into_owned(),
+                status: Some(meta.status.as_u16()),
+                source: Some(Box::new(source)),
+                context: Box::default(),
+            }
+            .with_request_id(meta.request_id.clone())
+        })?;
+
+        Ok(Self { data, meta })
+    }
+}
+
+impl<T> Deref for WithMeta<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T> DerefMut for WithMeta<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+/// Metadata of a response, see [`WithMeta`].
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// The HTTP status code.
+    pub status: StatusCode,
+    /// The `x-request-id` header, include it in support requests to Resend.
+    pub request_id: Option<String>,
+    /// The `ratelimit-*` headers.
+    pub rate_limit: RateLimitHeaders,
+    /// The `idempotent-replayed` header: `Some(true)` if the response was replayed for a repeated
+    /// idempotency key instead of performing the request again.
+    pub idempotent_replayed: Option<bool>,
+    /// All response headers.
+    pub headers: HeaderMap,
+}
+
+impl ResponseMeta {
+    pub(crate) fn new(status: StatusCode, headers: HeaderMap) -> Self {
+        let header = |name: HeaderName| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_owned())
+        };
+
+        Self {
+            status,
+            request_id: header(HeaderName::from_static("x-request-id")),
+            rate_limit: RateLimitHeaders::from_headers(&headers),
+            idempotent_replayed: header(HeaderName::from_static("idempotent-replayed"))
+                .and_then(|v| v.parse().ok()),
+            headers,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use reqwest::{StatusCode, header::HeaderMap};
+
+    use super::ResponseMeta;
+
+    #[test]
+    fn response_meta() {
+        let mut headers = HeaderMap::new();
+        let _old = headers.insert("x-request-id", "req_123".parse().unwrap());
+        let _old = headers.insert("ratelimit-remaining", "4".parse().unwrap());
+        let _old = headers.insert("idempotent-replayed", "true".parse().unwrap());
+
+        let meta = ResponseMeta::new(StatusCode::OK, headers);
+        assert_eq!(meta.status, StatusCode::OK);
+        assert_eq!(meta.request_id.as_deref(), Some("req_123"));
+        assert_eq!(meta.rate_limit.remaining, Some(4));
+        assert_eq!(meta.idempotent_replayed, Some(true));
+
+        let meta = ResponseMeta::new(StatusCode::CREATED, HeaderMap::new());
+        assert_eq!(meta.request_id, None);
+        assert_eq!(meta.idempotent_replayed, None);
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn undecodable_body() {
+        use crate::{ConfigBuilder, Error, Resend, test::ScriptedTransport};
+
+        let requests = ScriptedTransport::new(|_, _| {
+            let response = http::Response::builder()
+                .status(200)
+                .header("x-request-id", "req_123")
+                .body(r#"{"object":"email""#)
+                .unwrap();
+            Ok(response.into())
+        });
+        let config = ConfigBuilder::new("re_test")
+            .transport(requests)
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        let error = resend.emails.get("abc").await.unwrap_err();
+        let Error::Parse {
+            status, context, ..
+        } = &error
+        else {
+            panic!("expected a parse error, got {error:?}");
+        };
+        assert_eq!(*status, Some(200));
+        assert_eq!(context.method, Some(reqwest::Method::GET));
+        assert_eq!(context.endpoint.as_deref(), Some("/emails/abc"));
+        assert_eq!(context.request_id.as_deref(), Some("req_123"));
+        assert!(!error.is_retryable());
+    }
+}
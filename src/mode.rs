@@ -0,0 +1,419 @@
+//! Dry run and redirect modes for non-production environments.
+//!
+//! The [`Mode`] set with [`ConfigBuilder::mode`] decides what happens to the requests that send
+//! emails: [`EmailsSvc::send`], [`BatchSvc::send`], [`BroadcastsSvc::send`],
+//! [`BroadcastsSvc::create`] with [`CreateBroadcastOptions::with_send`] and [`EventsSvc::send`]
+//! (which can trigger automations), and their variants. All other requests are sent as usual.
+//!
+//! - [`Mode::Live`] (the default) sends them.
+//! - [`Mode::DryRun`] and [`Mode::Capture`] serialize and validate them as usual but never send
+//!   them, answering with synthetic ids instead. The payloads are logged (with the `tracing`
+//!   feature) or captured in an [`Outbox`].
+//! - [`Mode::Redirect`] rewrites every `to`, `cc` and `bcc` address of emails to a catch-all
+//!   address. Broadcasts and events reach the contacts of a segment or an automation, which can't
+//!   be redirected, so they are not sent at all.
+//!
+//! The mode is applied to the request as built by the service, before the
+//! [interceptors](crate::interceptor), the circuit breaker and the rate limit. Requests that are
+//! not sent thus don't use up the rate limit, and interceptors rewriting the url (e.g. for a
+//! proxy) can't make them slip through.
+//!
+//! ### Example
+//!
+//! ```rust
+//! # async fn run() -> resend_rs::Result<()> {
+//! use resend_rs::mode::{Mode, Outbox};
+//! use resend_rs::{ConfigBuilder, Resend, types::CreateEmailBaseOptions};
+//!
+//! let outbox = Outbox::new();
+//! let config = ConfigBuilder::new("re_...")
+//!     .mode(Mode::Capture(outbox.clone()))
+//!     .build();
+//! let resend = Resend::with_config(config);
+//!
+//! let email = CreateEmailBaseOptions::new("Acme <onboarding@a.dev>", ["steve@example.com"], "Hi");
+//! let _id = resend.emails.send(email).await?.id;
+//!
+//! let captured = outbox.take();
+//! assert_eq!(captured[0].body["to"][0], "steve@example.com");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ConfigBuilder::mode`]: crate::ConfigBuilder::mode
+//! [`EmailsSvc::send`]: crate::services::EmailsSvc::send
+//! [`BatchSvc::send`]: crate::services::BatchSvc::send
+//! [`BroadcastsSvc::send`]: crate::services::BroadcastsSvc::send
+//! [`BroadcastsSvc::create`]: crate::services::BroadcastsSvc::create
+//! [`CreateBroadcastOptions::with_send`]: crate::types::CreateBroadcastOptions::with_send
+//! [`EventsSvc::send`]: crate::services::EventsSvc::send
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use rand::RngExt;
+use reqwest::{Body, Request, Response};
+use serde_json::{Value, json};
+
+use crate::{Error, Result};
+
+/// The resend.dev address that accepts every email without delivering it, see
+/// <https://resend.com/docs/dashboard/emails/send-test-emails>.
+pub const TEST_ADDRESS: &str = "delivered@resend.dev";
+
+/// What to do with the requests that send emails, see the [module level documentation](self).
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub enum Mode {
+    /// Send emails.
+    #[default]
+    Live,
+    /// Don't send emails, answer with synthetic ids and log the payloads (with the `tracing`
+    /// feature).
+    DryRun,
+    /// The same as [`Mode::DryRun`], but also capture the payloads in the [`Outbox`].
+    Capture(Outbox),
+    /// Send emails to this address instead of their recipients, don't send broadcasts and events.
+    Redirect(String),
+}
+
+impl Mode {
+    /// [`Mode::Redirect`] to `address`.
+    pub fn redirect(address: impl Into<String>) -> Self {
+        Self::Redirect(address.into())
+    }
+
+    /// [`Mode::Redirect`] to the [`TEST_ADDRESS`].
+    pub fn redirect_to_test_address() -> Self {
+        Self::Redirect(TEST_ADDRESS.to_owned())
+    }
+
+    /// Applies the mode to a request to `endpoint` (relative to the base url).
+    ///
+    /// Returns the synthetic response if the request must not be sent.
+    pub(crate) fn apply(&self, endpoint: &str, request: &mut Request) -> Result<Option<Response>> {
+        let Some(kind) = Sending::from_request(endpoint, request) else {
+            return Ok(None);
+        };
+
+        let body = request
+            .body()
+            .and_then(Body::as_bytes)
+            .map(serde_json::from_slice::<Value>)
+            .transpose()
+            .map_err(|e| Error::other(format!("invalid request body: {e}")))?
+            .unwrap_or(Value::Null);
+        if matches!(kind, Sending::CreateBroadcast) && body.get("send") != Some(&Value::Bool(true))
+        {
+            return Ok(None);
+        }
+
+        let outbox = match self {
+            Self::Live => return Ok(None),
+            Self::Redirect(address) => {
+                let mut body = body;
+                match kind {
+                    Sending::Email => redirect(&mut body, address),
+                    Sending::Batch => body
+                        .as_array_mut()
+                        .into_iter()
+                        .flatten()
+                        .for_each(|email| redirect(email, address)),
+                    _ => return kind.response(&body).map(Some),
+                }
+                let body = serde_json::to_vec(&body)
+                    .map_err(|e| Error::other(format!("invalid request body: {e}")))?;
+                *request.body_mut() = Some(Body::from(body));
+
+                return Ok(None);
+            }
+            Self::DryRun => None,
+            Self::Capture(outbox) => Some(outbox),
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(endpoint, body = %body, "dry run, not sending request");
+
+        let response = kind.response(&body)?;
+        if let Some(outbox) = outbox {
+            outbox.push(Captured {
+                endpoint: endpoint.to_owned(),
+                body,
+            });
+        }
+
+        Ok(Some(response))
+    }
+}
+
+/// A request that sends emails.
+#[derive(Debug, Clone, Copy)]
+enum Sending<'a> {
+    Email,
+    Batch,
+    Broadcast(&'a str),
+    /// A broadcast that is sent once created, if its `send` field is `true`.
+    CreateBroadcast,
+    Event,
+}
+
+impl<'a> Sending<'a> {
+    fn from_request(endpoint: &'a str, request: &Request) -> Option<Self> {
+        if request.method() != reqwest::Method::POST {
+            return None;
+        }
+
+        match endpoint.trim_end_matches('/') {
+            "/emails" => Some(Self::Email),
+            "/emails/batch" => Some(Self::Batch),
+            "/broadcasts" => Some(Self::CreateBroadcast),
+            "/events/send" => Some(Self::Event),
+            endpoint => endpoint
+                .strip_prefix("/broadcasts/")
+                .and_then(|rest| rest.strip_suffix("/send"))
+                .filter(|id| !id.contains('/'))
+                .map(Self::Broadcast),
+        }
+    }
+
+    /// The synthetic response to the request with `body`.
+    fn response(self, body: &Value) -> Result<Response> {
+        let body = match self {
+            Self::Email | Self::CreateBroadcast => json!({ "id": new_id() }),
+            Self::Batch => {
+                let count = body.as_array().map_or(0, Vec::len);
+                let data = (0..count)
+                    .map(|_| json!({ "id": new_id() }))
+                    .collect::<Vec<_>>();
+                json!({ "data": data, "errors": [] })
+            }
+            Self::Broadcast(id) => json!({ "id": id }),
+            Self::Event => json!({ "event": body.get("event") }),
+        };
+
+        let response = http::Response::builder()
+            .status(200)
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .map_err(|e| Error::other(format!("invalid synthetic response: {e}")))?;
+
+        Ok(Response::from(response))
+    }
+}
+
+/// Replaces the recipients of `email` with `address`.
+fn redirect(email: &mut Value, address: &str) {
+    let Some(email) = email.as_object_mut() else {
+        return;
+    };
+
+    for field in ["to", "cc", "bcc"] {
+        if let Some(recipients) = email.get_mut(field).filter(|r| !r.is_null()) {
+            *recipients = json!([address]);
+        }
+    }
+}
+
+/// Random UUID (v4) like id.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn new_id() -> String {
+    let bytes = rand::rng().random::<u128>();
+    let hex = format!("{bytes:032x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        hex.get(0..8).unwrap_or_default(),
+        hex.get(8..12).unwrap_or_default(),
+        hex.get(12..16).unwrap_or_default(),
+        hex.get(16..20).unwrap_or_default(),
+        hex.get(20..32).unwrap_or_default(),
+    )
+}
+
+/// The payloads captured by [`Mode::Capture`], shared by all of its clones.
+#[derive(Debug, Clone, Default)]
+pub struct Outbox(Arc<Mutex<Vec<Captured>>>);
+
+impl Outbox {
+    /// Creates a new empty [`Outbox`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes and returns the captured payloads, oldest first.
+    pub fn take(&self) -> Vec<Captured> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    /// The amount of captured payloads.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no payloads have been captured.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn push(&self, captured: Captured) {
+        self.lock().push(captured);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Captured>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A payload that was not sent, see [`Outbox`].
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct Captured {
+    /// The endpoint the payload would have been sent to, e.g. `/emails`.
+    pub endpoint: String,
+    /// The JSON body of the request.
+    pub body: Value,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use serde_json::json;
+
+    use crate::{
+        ConfigBuilder, Error, Resend, Result,
+        mode::{Mode, Outbox},
+        test::ScriptedTransport,
+        types::{
+            ContactIdOrEmail, CreateBroadcastOptions, CreateEmailBaseOptions, SendBroadcastOptions,
+            SendEventOptions,
+        },
+    };
+
+    /// Fails every request.
+    fn unreachable() -> ScriptedTransport {
+        ScriptedTransport::new(|_, _| Err(Error::other("unreachable")))
+    }
+
+    fn resend(mode: Mode, transport: &ScriptedTransport) -> Resend {
+        let config = ConfigBuilder::new("re_test")
+            .transport(transport.clone())
+            .without_rate_limit()
+            .mode(mode)
+            .build();
+        Resend::with_config(config)
+    }
+
+    fn email() -> CreateEmailBaseOptions {
+        CreateEmailBaseOptions::new("a@a.dev", ["b@b.dev", "c@c.dev"], "Hi").with_cc("d@d.dev")
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn capture() {
+        let transport = unreachable();
+        let outbox = Outbox::new();
+        let resend = resend(Mode::Capture(outbox.clone()), &transport);
+
+        let id = resend.emails.send(email()).await.unwrap().id;
+        assert_eq!(id.len(), 36);
+        let ids = resend.batch.send(vec![email(), email()]).await.unwrap();
+        assert_eq!(ids.len(), 2);
+        let broadcast = SendBroadcastOptions::new("abc");
+        assert_eq!(
+            resend.broadcasts.send(broadcast).await.unwrap().id.as_ref(),
+            "abc"
+        );
+        assert!(resend.emails.get("abc").await.is_err());
+
+        let captured = outbox.take();
+        let endpoints: Vec<_> = captured.iter().map(|c| c.endpoint.as_str()).collect();
+        assert_eq!(
+            endpoints,
+            ["/emails", "/emails/batch", "/broadcasts/abc/send"]
+        );
+        let to = captured.first().unwrap().body.pointer("/to/1");
+        assert_eq!(to, Some(&json!("c@c.dev")));
+        assert!(outbox.is_empty());
+        // Only the `get` was sent
+        assert_eq!(transport.calls(), 1);
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn redirect() {
+        let transport = unreachable();
+        let resend = resend(Mode::redirect_to_test_address(), &transport);
+
+        assert!(resend.emails.send(email()).await.is_err());
+        assert!(resend.batch.send(vec![email()]).await.is_err());
+        let broadcast = SendBroadcastOptions::new("abc");
+        assert!(resend.broadcasts.send(broadcast).await.is_ok());
+
+        let sent = transport.bodies();
+        let [email, batch] = sent.as_slice() else {
+            panic!("expected 2 requests, got {sent:?}");
+        };
+        assert_eq!(email["to"], json!(["delivered@resend.dev"]));
+        assert_eq!(email["cc"], json!(["delivered@resend.dev"]));
+        assert!(email.get("bcc").is_none());
+        let to = batch.pointer("/0/to");
+        assert_eq!(to, Some(&json!(["delivered@resend.dev"])));
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn broadcast_create_and_event() {
+        for mode in [Mode::DryRun, Mode::redirect_to_test_address()] {
+            let transport = unreachable();
+            let resend = resend(mode, &transport);
+
+            let broadcast = CreateBroadcastOptions::new("s", "a@a.dev", "Hi").with_send(true);
+            let created = resend.broadcasts.create(broadcast.clone()).await.unwrap();
+            assert_eq!(created.id.as_ref().len(), 36);
+            let event = SendEventOptions {
+                event: "user.signed_up".to_owned(),
+                contact_id_or_email: ContactIdOrEmail::Email("b@b.dev".to_owned()),
+                payload: json!({}),
+            };
+            let sent = resend.events.send(event).await.unwrap();
+            assert_eq!(sent.event, "user.signed_up");
+            assert_eq!(transport.calls(), 0);
+
+            // Drafts are still created
+            let draft = broadcast.with_send(false);
+            assert!(resend.broadcasts.create(draft).await.is_err());
+            assert_eq!(transport.calls(), 1);
+        }
+    }
+
+    /// Sends every request through a proxy, like a corporate one.
+    #[derive(Debug)]
+    struct Proxy;
+
+    #[async_trait::async_trait]
+    impl crate::interceptor::Interceptor for Proxy {
+        async fn on_request(
+            &self,
+            mut request: reqwest::Request,
+        ) -> Result<crate::interceptor::Action> {
+            let url = format!("http://proxy.local/resend{}", request.url().path());
+            *request.url_mut() = url.parse().unwrap();
+            Ok(crate::interceptor::Action::Continue(request))
+        }
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn dry_run_with_rewriting_interceptor() {
+        let transport = unreachable();
+        let config = ConfigBuilder::new("re_test")
+            .transport(transport.clone())
+            .interceptor(Proxy)
+            .without_rate_limit()
+            .mode(Mode::DryRun)
+            .build();
+        let resend = Resend::with_config(config);
+
+        assert!(resend.emails.send(email()).await.is_ok());
+        assert!(resend.batch.send(vec![email()]).await.is_ok());
+        assert_eq!(transport.calls(), 0);
+
+        // Other requests still go through the interceptor
+        assert!(resend.emails.get("abc").await.is_err());
+        assert_eq!(transport.calls(), 1);
+    }
+}
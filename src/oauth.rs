@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::{OAuthGrant, RevokeOAuthGrantResponse},
 };
 
@@ -12,31 +13,41 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct OAuthSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl OAuthSvc {
     /// Retrieve a list of OAuth grants for the authenticated team.
     ///
     /// - Default limit: *infinite*
     ///
     /// <https://resend.com/docs/api-reference/oauth/list-grants>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<OAuthGrant>> {
         let request = self.0.build(Method::GET, "/oauth/grants").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<OAuthGrant>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<OAuthGrant>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<OAuthGrant> {
+        Paginator::new(Arc::clone(&self.0), "/oauth/grants".to_owned(), &list_opts)
+    }
+
     /// Revoke an OAuth grant for the authenticated team.
     ///
     /// <https://resend.com/docs/api-reference/oauth/revoke-grant>
-    #[maybe_async::maybe_async]
     pub async fn revoke(&self, oauth_grant_id: &str) -> Result<RevokeOAuthGrantResponse> {
         let path = format!("/oauth/grants/{oauth_grant_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<RevokeOAuthGrantResponse>().await?;
+        let content = self
+            .0
+            .send_json::<RevokeOAuthGrantResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -80,7 +91,6 @@ pub mod types {
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -92,7 +102,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
 
@@ -0,0 +1,344 @@
+//! Pool of [`Resend`] clients for sending on behalf of many accounts.
+//!
+//! A [`ResendPool`] lazily creates one client per API key (or OAuth access token). All of them
+//! share a single `reqwest` client and thus its connection pool, but each has its own rate
+//! limiter, since Resend enforces rate limits per account.
+//!
+//! The pool can limit how many clients it keeps ([`ResendPoolBuilder::max_tenants`]), evict
+//! clients that have not been used for a while ([`ResendPoolBuilder::idle_timeout`]) and cap the
+//! number of requests in flight across all of them ([`ResendPoolBuilder::max_concurrency`]).
+//!
+//! ### Example
+//!
+//! ```rust,no_run
+//! # async fn run() -> resend_rs::Result<()> {
+//! use std::{num::NonZeroU32, time::Duration};
+//!
+//! use resend_rs::{pool::ResendPool, rate_limit::Quota, types::CreateEmailBaseOptions};
+//!
+//! let pool = ResendPool::builder()
+//!     .max_tenants(1_000)
+//!     .idle_timeout(Duration::from_secs(15 * 60))
+//!     .max_concurrency(64)
+//!     .configure(|config| config.rate_limit(Quota::per_second(NonZeroU32::MIN.saturating_add(1))))
+//!     .build();
+//!
+//! let email = CreateEmailBaseOptions::new("Acme <onboarding@a.dev>", ["delivered@resend.dev"], "Hi")
+//!     .with_text("Hello World!");
+//! let _sent = pool.get("re_customer_key").emails.send(email).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use async_lock::Semaphore;
+use reqwest::Client as ReqwestClient;
+use reqwest::{Request, Response};
+
+use crate::{ConfigBuilder, Resend, Result, transport::Transport};
+
+type Configure = Arc<dyn Fn(ConfigBuilder) -> ConfigBuilder + Send + Sync>;
+
+/// Builder for a [`ResendPool`].
+#[must_use]
+#[derive(Default)]
+pub struct ResendPoolBuilder {
+    client: Option<ReqwestClient>,
+    configure: Option<Configure>,
+    max_tenants: Option<usize>,
+    idle_timeout: Option<Duration>,
+    max_concurrency: Option<usize>,
+}
+
+impl ResendPoolBuilder {
+    /// Creates a new [`ResendPoolBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `reqwest` client shared by every client of the pool.
+    pub fn client(mut self, client: ReqwestClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Customizes the [`ConfigBuilder`] of every client created by the pool, e.g. to set a
+    /// [`Quota`](crate::rate_limit::Quota) or a [`RetryPolicy`](crate::rate_limit::RetryPolicy).
+    ///
+    /// Every client still gets its own rate limiter unless a shared one is set with
+    /// [`ConfigBuilder::limiter`].
+    pub fn configure(
+        mut self,
+        configure: impl Fn(ConfigBuilder) -> ConfigBuilder + Send + Sync + 'static,
+    ) -> Self {
+        self.configure = Some(Arc::new(configure));
+        self
+    }
+
+    /// Sets the maximum amount of clients kept by the pool. The least recently used client is
+    /// evicted when a new one would exceed it.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `max_tenants` is `0`.
+    pub const fn max_tenants(mut self, max_tenants: usize) -> Self {
+        assert!(max_tenants > 0, "max_tenants should not be 0");
+        self.max_tenants = Some(max_tenants);
+        self
+    }
+
+    /// Evicts clients that have not been used for `idle_timeout`.
+    pub const fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Sets the maximum amount of requests in flight across all clients of the pool.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `max_concurrency` is `0`.
+    pub const fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        assert!(max_concurrency > 0, "max_concurrency should not be 0");
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+
+    /// Builds the [`ResendPool`].
+    pub fn build(self) -> ResendPool {
+        ResendPool {
+            client: self.client.unwrap_or_default(),
+            configure: self.configure,
+            max_tenants: self.max_tenants,
+            idle_timeout: self.idle_timeout,
+            semaphore: self
+                .max_concurrency
+                .map(|max| Arc::new(Semaphore::new(max))),
+            tenants: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl fmt::Debug for ResendPoolBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResendPoolBuilder")
+            .field("max_tenants", &self.max_tenants)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("max_concurrency", &self.max_concurrency)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Lazily created [`Resend`] clients keyed by API key, see the [module level
+/// documentation](self).
+pub struct ResendPool {
+    client: ReqwestClient,
+    configure: Option<Configure>,
+    max_tenants: Option<usize>,
+    idle_timeout: Option<Duration>,
+    semaphore: Option<Arc<Semaphore>>,
+    tenants: Mutex<HashMap<String, Tenant>>,
+}
+
+struct Tenant {
+    client: Resend,
+    last_used: Instant,
+}
+
+impl ResendPool {
+    /// Creates a new [`ResendPoolBuilder`].
+    pub fn builder() -> ResendPoolBuilder {
+        ResendPoolBuilder::new()
+    }
+
+    /// Returns the client for `api_key`, creating it if necessary.
+    ///
+    /// Clients are cheap to clone and keep working after being evicted from the pool, but a
+    /// client created after an eviction starts with a fresh rate limiter.
+    ///
+    /// ### Panics
+    ///
+    /// See [`ConfigBuilder::build`].
+    pub fn get(&self, api_key: &str) -> Resend {
+        let now = Instant::now();
+        let mut tenants = self.tenants.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(idle_timeout) = self.idle_timeout {
+            tenants.retain(|_, tenant| now.duration_since(tenant.last_used) < idle_timeout);
+        }
+
+        if let Some(tenant) = tenants.get_mut(api_key) {
+            tenant.last_used = now;
+            return tenant.client.clone();
+        }
+
+        if let Some(max_tenants) = self.max_tenants {
+            while tenants.len() >= max_tenants {
+                let Some(lru) = tenants
+                    .iter()
+                    .min_by_key(|(_, tenant)| tenant.last_used)
+                    .map(|(key, _)| key.clone())
+                else {
+                    break;
+                };
+                let _evicted = tenants.remove(&lru);
+            }
+        }
+
+        let client = self.create(api_key);
+        let _previous = tenants.insert(
+            api_key.to_owned(),
+            Tenant {
+                client: client.clone(),
+                last_used: now,
+            },
+        );
+        drop(tenants);
+
+        client
+    }
+
+    /// Removes the client for `api_key` from the pool, returns whether it was present.
+    pub fn evict(&self, api_key: &str) -> bool {
+        self.tenants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(api_key)
+            .is_some()
+    }
+
+    /// Returns the amount of clients in the pool.
+    pub fn len(&self) -> usize {
+        self.tenants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns `true` if the pool has no clients.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn create(&self, api_key: &str) -> Resend {
+        let builder = ConfigBuilder::new(api_key).client(self.client.clone());
+        let builder = match self.configure {
+            Some(ref configure) => configure(builder),
+            None => builder,
+        };
+
+        let mut config = builder.build();
+        if let Some(ref semaphore) = self.semaphore {
+            config.transport = Arc::new(ConcurrencyLimit {
+                inner: config.transport,
+                semaphore: Arc::clone(semaphore),
+            });
+        }
+
+        Resend::with_config(config)
+    }
+}
+
+impl Default for ResendPool {
+    fn default() -> Self {
+        ResendPoolBuilder::new().build()
+    }
+}
+
+impl fmt::Debug for ResendPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Don't output API keys.
+        f.debug_struct("ResendPool")
+            .field("tenants", &self.len())
+            .field("max_tenants", &self.max_tenants)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("semaphore", &self.semaphore)
+            .finish_non_exhaustive()
+    }
+}
+
+/// [`Transport`] holding a permit of the shared [`Semaphore`] while a request is in flight.
+#[derive(Debug)]
+struct ConcurrencyLimit {
+    inner: Arc<dyn Transport>,
+    semaphore: Arc<Semaphore>,
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Transport for ConcurrencyLimit {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let permit = self.semaphore.acquire().await;
+        let response = self.inner.execute(request).await;
+        drop(permit);
+
+        response
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::time::Duration;
+
+    use super::ResendPool;
+
+    #[test]
+    fn eviction() {
+        let pool = ResendPool::builder().max_tenants(2).build();
+
+        let a = pool.get("re_a");
+        assert_eq!(a.api_key(), "re_a");
+        let _b = pool.get("re_b");
+        assert_eq!(pool.len(), 2);
+
+        // `re_a` is the most recently used one
+        let _a = pool.get("re_a");
+        let _c = pool.get("re_c");
+        assert_eq!(pool.len(), 2);
+        assert!(!pool.evict("re_b"));
+        assert!(pool.evict("re_c"));
+        assert_eq!(pool.len(), 1);
+
+        let pool = ResendPool::builder().idle_timeout(Duration::ZERO).build();
+        let _a = pool.get("re_a");
+        let _b = pool.get("re_b");
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn max_concurrency() {
+        use crate::test::ScriptedTransport;
+
+        let body = r#"{"id":"abc","url":"https://resend.com/share/abc"}"#;
+        let slow = ScriptedTransport::respond(200, body).with_delay(Duration::from_millis(20));
+        let transport = slow.clone();
+        let pool = ResendPool::builder()
+            .max_concurrency(2)
+            .configure(move |config| config.transport(transport.clone()).without_rate_limit())
+            .build();
+
+        let requests = (0..8).map(|i| {
+            let resend = pool.get(&format!("re_{}", i % 4));
+            async move {
+                resend
+                    .emails
+                    .share("abc", crate::types::ShareEmailOptions::new())
+                    .await
+            }
+        });
+        let handles = requests.map(tokio::spawn).collect::<Vec<_>>();
+        for handle in handles {
+            let _shared = handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(pool.len(), 4);
+        assert_eq!(slow.max_in_flight(), 2);
+    }
+}
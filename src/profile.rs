@@ -0,0 +1,165 @@
+//! Loading a [`ConfigBuilder`] from a TOML profile file.
+
+use std::{
+    collections::HashMap,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    ConfigBuilder, ConfigError,
+    config::{api_key_from_file, api_key_from_vars, parse_base_url},
+    rate_limit::Quota,
+};
+
+#[derive(Debug, Deserialize)]
+struct ProfileFile {
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    api_key: Option<String>,
+    api_key_file: Option<PathBuf>,
+    base_url: Option<String>,
+    rate_limit: Option<NonZeroU32>,
+}
+
+impl ConfigBuilder {
+    /// Create new [`ConfigBuilder`] from the profile `name` of the TOML file at `path`.
+    ///
+    /// Profiles are tables under `profiles` and may set:
+    ///
+    /// - `api_key`, or `api_key_file` with the path to a file holding the API key. Relative paths
+    ///   are resolved against the directory of the profile file.
+    /// - `base_url`.
+    /// - `rate_limit`, the burst of the default [`Quota`].
+    ///
+    /// Settings missing from the profile are read from the environment variables, see
+    /// [`ConfigBuilder::from_env`].
+    ///
+    /// ```toml
+    /// [profiles.production]
+    /// api_key_file = "/run/secrets/resend_api_key"
+    ///
+    /// [profiles.staging]
+    /// api_key = "re_..."
+    /// base_url = "https://resend-proxy.staging.internal"
+    /// rate_limit = 2
+    /// ```
+    ///
+    /// ```no_run
+    /// # fn run() -> Result<(), resend_rs::ConfigError> {
+    /// use resend_rs::{ConfigBuilder, Resend};
+    ///
+    /// let config = ConfigBuilder::from_profile("resend.toml", "staging")?.try_build()?;
+    /// let resend = Resend::with_config(config);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_profile(path: impl AsRef<Path>, name: &str) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|source| ConfigError::ProfileFile {
+            path: path.to_owned(),
+            source,
+        })?;
+
+        Self::from_profile_str(path, &content, name)
+    }
+
+    fn from_profile_str(path: &Path, content: &str, name: &str) -> Result<Self, ConfigError> {
+        let mut file: ProfileFile =
+            toml::from_str(content).map_err(|source| ConfigError::InvalidProfileFile {
+                path: path.to_owned(),
+                source: Box::new(source),
+            })?;
+        let profile = file
+            .profiles
+            .remove(name)
+            .ok_or_else(|| ConfigError::UnknownProfile {
+                path: path.to_owned(),
+                name: name.to_owned(),
+            })?;
+
+        let api_key = match (profile.api_key, profile.api_key_file) {
+            (Some(api_key), _) => api_key,
+            (None, Some(api_key_file)) => {
+                let dir = path.parent().unwrap_or_else(|| Path::new(""));
+                api_key_from_file(dir.join(api_key_file))?
+            }
+            (None, None) => api_key_from_vars(&std::env::var::<&str>)?,
+        };
+
+        let mut builder = Self::new(api_key);
+        if let Some(base_url) = profile.base_url {
+            builder = builder.base_url(parse_base_url(base_url)?);
+        }
+        if let Some(burst) = profile.rate_limit {
+            builder = builder.rate_limit(Quota::default().with_burst(burst));
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::path::Path;
+
+    use crate::{ConfigBuilder, ConfigError, Resend};
+
+    const PROFILES: &str = r#"
+        [profiles.staging]
+        api_key = "re_staging"
+        base_url = "https://staging.resend.test"
+        rate_limit = 2
+
+        [profiles.production]
+        api_key_file = "resend_api_key"
+    "#;
+
+    #[test]
+    fn profiles() {
+        let path = Path::new("resend.toml");
+        let config = ConfigBuilder::from_profile_str(path, PROFILES, "staging")
+            .unwrap()
+            .build();
+        let resend = Resend::with_config(config);
+        assert_eq!(resend.api_key(), "re_staging");
+        assert_eq!(resend.base_url(), "https://staging.resend.test/");
+
+        let dir = std::env::temp_dir().join(format!("resend-profile-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("resend_api_key"), "re_production\n").unwrap();
+        let path = dir.join("resend.toml");
+        std::fs::write(&path, PROFILES).unwrap();
+
+        let config = ConfigBuilder::from_profile(&path, "production")
+            .unwrap()
+            .build();
+        assert_eq!(Resend::with_config(config).api_key(), "re_production");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_profiles() {
+        let path = Path::new("resend.toml");
+        let res = ConfigBuilder::from_profile_str(path, PROFILES, "dev");
+        assert!(matches!(res, Err(ConfigError::UnknownProfile { name, .. }) if name == "dev"));
+
+        let res = ConfigBuilder::from_profile_str(path, "[profiles.dev]\napi_kye = \"re\"", "dev");
+        assert!(matches!(res, Err(ConfigError::InvalidProfileFile { .. })));
+
+        let res = ConfigBuilder::from_profile_str(path, "[profiles.dev]\nrate_limit = 0", "dev");
+        assert!(matches!(res, Err(ConfigError::InvalidProfileFile { .. })));
+
+        let content = "[profiles.dev]\napi_key = \"re\"\nbase_url = \"resend\"";
+        let res = ConfigBuilder::from_profile_str(path, content, "dev");
+        assert!(matches!(res, Err(ConfigError::InvalidBaseUrl { .. })));
+    }
+}
@@ -2,10 +2,28 @@
 //!
 //! The [`retry!`](crate::retry!) and [`retry_opts!`](crate::retry_opts) macros are also implemented
 //! as slightly-less-verbose alternatives.
+//!
+//! For retrying *every* request made by a client, see [`RetryPolicy`] instead. The client side
+//! rate limiting applied before each request is configured with a [`Quota`] or a custom
+//! [`Limiter`].
 
 use crate::{Error, Result};
 use rand::RngExt;
-use std::{future::Future, ops::Range, time::Duration};
+use reqwest::{Method, header::HeaderMap};
+use std::{num::NonZeroU32, ops::Range, time::Duration};
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    time::Instant,
+};
+
+use governor::{
+    RateLimiter,
+    clock::MonotonicClock,
+    middleware::NoOpMiddleware,
+    state::{InMemoryState, NotKeyed},
+};
 
 /// Configuration options for retrying requests.
 #[derive(Debug, Clone)]
@@ -18,6 +36,16 @@ pub struct RetryOptions {
     pub max_retries: u32,
 }
 
+impl RetryOptions {
+    /// Returns the delay before the next retry, `ratelimit_reset` (in seconds) takes precedence
+    /// over [`RetryOptions::duration_ms`].
+    pub(crate) fn delay(&self, ratelimit_reset: Option<u64>) -> Duration {
+        let sleep_millis = ratelimit_reset.map_or(self.duration_ms, |r| r.saturating_mul(1000));
+        let jitter = rand::rng().random_range(self.jitter_range_ms.clone());
+        Duration::from_millis(sleep_millis.saturating_add(jitter))
+    }
+}
+
 impl Default for RetryOptions {
     fn default() -> Self {
         Self {
@@ -28,6 +56,467 @@ impl Default for RetryOptions {
     }
 }
 
+/// Automatic retry policy applied by the client to *every* request.
+///
+/// Set with [`ConfigBuilder::retry_policy`](crate::ConfigBuilder::retry_policy), by default
+/// requests are not retried.
+///
+/// A request is retried when it fails with:
+///
+/// - a `429 Too Many Requests` ([`Error::RateLimit`]), but not a spent daily or monthly quota
+///   (an [`Error::Resend`] named `daily_quota_exceeded` or `monthly_quota_exceeded`), which
+///   retrying can't fix,
+/// - a `5xx` server error,
+/// - a connection error or a timeout.
+///
+/// The latter two are only retried for idempotent requests, i.e. `GET` and `DELETE` requests as
+/// well as requests carrying an [idempotency key](crate::idempotent), since the request may
+/// already have been processed. Retrying them anyway (and risking e.g. duplicate emails) can be
+/// enabled with [`RetryPolicy::with_retry_non_idempotent`].
+///
+/// The delay before each retry is taken from the `ratelimit-reset` or `retry-after` response
+/// headers if present, otherwise an exponential backoff with jitter is used.
+///
+/// Requests with a streaming body cannot be cloned and are therefore never retried.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use resend_rs::{ConfigBuilder, Resend, rate_limit::RetryPolicy};
+///
+/// let policy = RetryPolicy::default()
+///   .with_max_retries(5)
+///   .with_max_backoff(Duration::from_secs(10));
+///
+/// let config = ConfigBuilder::new("re_...").retry_policy(policy).build();
+/// let resend = Resend::with_config(config);
+/// ```
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Maximum amount of retries before returning an error, `0` disables retrying.
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled with every subsequent retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the exponential backoff and of the delays requested by the server.
+    pub max_backoff: Duration,
+    /// Whether non idempotent requests are retried on `5xx` responses, connection errors and
+    /// timeouts as well.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Retries up to 3 times, starting at 500ms and backing off up to 30s.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A [`RetryPolicy`] that never retries.
+    #[inline]
+    pub const fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            retry_non_idempotent: false,
+        }
+    }
+
+    /// Sets the maximum amount of retries.
+    #[inline]
+    pub const fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    #[inline]
+    pub const fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the upper bound of the exponential backoff and of the delays requested by the server.
+    #[inline]
+    pub const fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets whether non idempotent requests are retried on `5xx` responses, connection errors
+    /// and timeouts as well.
+    #[inline]
+    pub const fn with_retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    /// Returns whether a request may be retried after a `5xx` response, a connection error or a
+    /// timeout.
+    pub(crate) fn retries(&self, method: &Method, headers: &HeaderMap) -> bool {
+        self.retry_non_idempotent
+            || matches!(*method, Method::GET | Method::DELETE)
+            || headers.contains_key("idempotency-key")
+    }
+
+    /// Returns the delay before retry number `attempt` (starting at `0`).
+    ///
+    /// `retry_after` is the delay requested by the server (if any), which takes precedence over
+    /// the exponential backoff but is capped at [`RetryPolicy::max_backoff`] as well.
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let mut rng = rand::rng();
+
+        if let Some(retry_after) = retry_after {
+            // Small jitter so that parallel requests don't all wake up at once.
+            let jitter = Duration::from_millis(rng.random_range(0..100));
+            return retry_after.saturating_add(jitter).min(self.max_backoff);
+        }
+
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff);
+
+        // "Equal jitter": half of the backoff is fixed, the other half is random.
+        let half = backoff / 2;
+        let jitter_ms = u64::try_from(half.as_millis()).unwrap_or(u64::MAX);
+        half + Duration::from_millis(rng.random_range(0..=jitter_ms))
+    }
+}
+
+/// Client side rate limit applied before every request.
+///
+/// Allows `burst` requests at once, after which `rate` requests are allowed per `period`.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use std::{num::NonZeroU32, time::Duration};
+/// use resend_rs::{ConfigBuilder, rate_limit::Quota};
+///
+/// // 5 requests per second, at most 10 at once
+/// let quota = Quota::per_second(NonZeroU32::new(5).unwrap())
+///   .with_burst(NonZeroU32::new(10).unwrap());
+///
+/// let config = ConfigBuilder::new("re_...").rate_limit(quota).build();
+/// ```
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    rate: NonZeroU32,
+    period: Duration,
+    burst: NonZeroU32,
+}
+
+impl Default for Quota {
+    /// Bursts of up to 9 requests, replenishing one request every 1.1s.
+    fn default() -> Self {
+        Self {
+            rate: NonZeroU32::MIN,
+            period: Duration::from_millis(1100),
+            burst: NonZeroU32::MIN.saturating_add(8),
+        }
+    }
+}
+
+impl Quota {
+    /// Allows `rate` requests per `period`, with a burst of `rate`.
+    #[inline]
+    pub const fn new(rate: NonZeroU32, period: Duration) -> Self {
+        Self {
+            rate,
+            period,
+            burst: rate,
+        }
+    }
+
+    /// Allows `rate` requests per second, with a burst of `rate`.
+    #[inline]
+    pub const fn per_second(rate: NonZeroU32) -> Self {
+        Self::new(rate, Duration::from_secs(1))
+    }
+
+    /// Sets the maximum amount of requests allowed at once.
+    #[inline]
+    pub const fn with_burst(mut self, burst: NonZeroU32) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// The amount of requests allowed per [`Quota::period`].
+    #[must_use]
+    #[inline]
+    pub const fn rate(&self) -> NonZeroU32 {
+        self.rate
+    }
+
+    /// The period in which [`Quota::rate`] requests are allowed.
+    #[must_use]
+    #[inline]
+    pub const fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// The maximum amount of requests allowed at once.
+    #[must_use]
+    #[inline]
+    pub const fn burst(&self) -> NonZeroU32 {
+        self.burst
+    }
+
+    /// The stricter of both quotas: the lower sustained rate and the lower burst.
+    fn min(self, other: Self) -> Self {
+        // `rate / period` compared without dividing.
+        let rate = u128::from(self.rate.get()) * other.period.as_nanos();
+        let other_rate = u128::from(other.rate.get()) * self.period.as_nanos();
+        let slower = if rate <= other_rate { self } else { other };
+
+        Self {
+            burst: self.burst.min(other.burst),
+            ..slower
+        }
+    }
+
+    fn to_governor(self) -> governor::Quota {
+        let replenish = self.period / self.rate.get();
+
+        governor::Quota::with_period(replenish.max(Duration::from_nanos(1)))
+            .unwrap_or_else(|| governor::Quota::per_second(self.rate))
+            .allow_burst(self.burst)
+    }
+}
+
+/// The rate limit headers returned by Resend with every response.
+///
+/// <https://resend.com/docs/api-reference/introduction#rate-limit>
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitHeaders {
+    /// `ratelimit-limit`: maximum amount of requests allowed within a window.
+    pub limit: Option<u64>,
+    /// `ratelimit-remaining`: how many requests are left within the current window.
+    pub remaining: Option<u64>,
+    /// `ratelimit-reset`: seconds until the current window resets.
+    pub reset: Option<u64>,
+    /// The window (`w`) of the `ratelimit-policy` header, in seconds.
+    pub window: Option<u64>,
+}
+
+impl RateLimitHeaders {
+    /// Parses the rate limit headers out of `headers`.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+        let header_u64 = |name| header(name).and_then(|v| v.trim().parse::<u64>().ok());
+
+        // e.g. `10;w=1`
+        let window = header("ratelimit-policy").and_then(|policy| {
+            policy
+                .split(';')
+                .filter_map(|param| param.trim().strip_prefix("w="))
+                .find_map(|w| w.parse().ok())
+        });
+
+        Self {
+            limit: header_u64("ratelimit-limit"),
+            remaining: header_u64("ratelimit-remaining"),
+            reset: header_u64("ratelimit-reset"),
+            window,
+        }
+    }
+}
+
+/// Client side rate limiter awaited before every request.
+///
+/// The built-in implementation is [`QuotaLimiter`], a custom implementation can be used to
+/// coordinate limits between processes (through a file lock, Redis, ...). A limiter can be shared
+/// between several clients using the same API key with
+/// [`ConfigBuilder::limiter`](crate::ConfigBuilder::limiter).
+///
+/// Async implementations are declared with [`async_trait`](https://docs.rs/async-trait).
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait Limiter: fmt::Debug + Send + Sync {
+    /// Waits until the next request is allowed to be sent.
+    ///
+    /// Returning an error aborts the request with that error.
+    async fn until_ready(&self) -> Result<()>;
+
+    /// Called with the rate limit headers of every response, including `429`s.
+    ///
+    /// Does nothing by default.
+    fn update(&self, _headers: &RateLimitHeaders) {}
+}
+
+type GovernorLimiter = RateLimiter<
+    NotKeyed,
+    InMemoryState,
+    MonotonicClock,
+    NoOpMiddleware<<MonotonicClock as governor::clock::Clock>::Instant>,
+>;
+
+/// In-memory [`Limiter`] enforcing a [`Quota`].
+///
+/// By default the limiter adapts to the rate limit headers of every response:
+///
+/// - when the `ratelimit-limit` is stricter than the configured [`Quota`] (for example because
+///   the quota of the account is lower), the limit is enforced instead, keeping a 10% margin. The
+///   configured quota is never exceeded, whatever the server sends,
+/// - once `ratelimit-remaining` drops to 10% of `ratelimit-limit` or less, requests are spread
+///   out over the time left until `ratelimit-reset` (up to a minute), and paused until the reset
+///   once nothing remains.
+///
+/// This can be turned off with [`QuotaLimiter::with_adaptive`].
+///
+/// ## Example
+///
+/// Sharing one limiter between two clients:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use resend_rs::{ConfigBuilder, Resend, rate_limit::{Quota, QuotaLimiter}};
+///
+/// let limiter = Arc::new(QuotaLimiter::new(Quota::default()));
+///
+/// let a = ConfigBuilder::new("re_...").limiter(limiter.clone()).build();
+/// let b = ConfigBuilder::new("re_...").limiter(limiter).build();
+/// ```
+pub struct QuotaLimiter {
+    /// The quota the limiter was created with, an upper bound of the enforced one.
+    configured: Quota,
+    state: RwLock<(Quota, Arc<GovernorLimiter>)>,
+    /// No request is let through before this instant.
+    paused_until: Mutex<Option<Instant>>,
+    adaptive: bool,
+}
+
+impl QuotaLimiter {
+    /// Creates a new adaptive [`QuotaLimiter`] enforcing `quota`.
+    pub fn new(quota: Quota) -> Self {
+        Self {
+            configured: quota,
+            state: RwLock::new((quota, Self::governor(quota))),
+            paused_until: Mutex::new(None),
+            adaptive: true,
+        }
+    }
+
+    /// Sets whether the limiter adapts to the rate limit headers of the responses.
+    #[must_use]
+    pub const fn with_adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    /// The longest the limiter pauses for after a response with few requests remaining, so
+    /// that a bogus `ratelimit-reset` can't stall the client.
+    const MAX_PAUSE: Duration = Duration::from_mins(1);
+
+    /// The currently enforced [`Quota`].
+    pub fn quota(&self) -> Quota {
+        self.state.read().unwrap_or_else(PoisonError::into_inner).0
+    }
+
+    fn governor(quota: Quota) -> Arc<GovernorLimiter> {
+        Arc::new(RateLimiter::direct_with_clock(
+            quota.to_governor(),
+            MonotonicClock,
+        ))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Limiter for QuotaLimiter {
+    async fn until_ready(&self) -> Result<()> {
+        let paused_until = *self
+            .paused_until
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(paused) =
+            paused_until.and_then(|until| until.checked_duration_since(Instant::now()))
+        {
+            sleep(paused).await;
+        }
+
+        let limiter = Arc::clone(&self.state.read().unwrap_or_else(PoisonError::into_inner).1);
+        governor_until_ready(&limiter).await;
+        Ok(())
+    }
+
+    fn update(&self, headers: &RateLimitHeaders) {
+        if !self.adaptive {
+            return;
+        }
+
+        // Follow the account's quota when it is stricter than the configured one.
+        let limit = headers
+            .limit
+            .and_then(|limit| u32::try_from(limit).ok())
+            .and_then(NonZeroU32::new);
+        if let Some(limit) = limit {
+            // Windows longer than an hour are bogus, and too long for the governor.
+            let window = Duration::from_secs(headers.window.unwrap_or(1).clamp(1, 3600));
+            let quota = self.configured.min(Quota::new(limit, window + window / 10));
+
+            let mut state = self.state.write().unwrap_or_else(PoisonError::into_inner);
+            if state.0 != quota {
+                *state = (quota, Self::governor(quota));
+            }
+        }
+
+        // Spread the remaining requests over the rest of the window once at most 10% of the
+        // limit (or of the burst of the quota without a `ratelimit-limit`) remains.
+        let limit = headers
+            .limit
+            .unwrap_or_else(|| u64::from(self.quota().burst().get()));
+        if let (Some(remaining), Some(reset)) = (headers.remaining, headers.reset)
+            && remaining.saturating_mul(10) <= limit
+        {
+            let delay = Duration::from_secs(reset)
+                / u32::try_from(remaining.saturating_add(1)).unwrap_or(u32::MAX);
+            let Some(until) = Instant::now().checked_add(delay.min(Self::MAX_PAUSE)) else {
+                return;
+            };
+
+            let mut paused_until = self
+                .paused_until
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if paused_until.is_none_or(|paused| paused < until) {
+                *paused_until = Some(until);
+            }
+        }
+    }
+}
+
+/// Waits until `limiter` allows a request, without blocking the executor.
+async fn governor_until_ready(limiter: &GovernorLimiter) {
+    let jitter = governor::Jitter::new(Duration::from_millis(10), Duration::from_millis(50));
+    limiter.until_ready_with_jitter(jitter).await;
+}
+
+impl fmt::Debug for QuotaLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuotaLimiter")
+            .field("quota", &self.quota())
+            .field("adaptive", &self.adaptive)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sleeps for `duration` without blocking the executor.
+pub(crate) async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await;
+}
+
 #[allow(clippy::too_long_first_doc_paragraph)] // It really is not that long though
 /// Helper method that executes the passed function. If the function returns [`Ok`],
 /// or a non-rate limit related [`Err`] the result is returned immediately. If the function
@@ -37,7 +526,7 @@ impl Default for RetryOptions {
 ///
 /// Listing your API keys:
 ///
-/// ```rust
+/// ```rust,no_run
 /// use resend_rs::{
 ///  rate_limit::{send_with_retry, send_with_retry_opts, RetryOptions},
 ///  types::CreateEmailBaseOptions,
@@ -58,7 +547,7 @@ impl Default for RetryOptions {
 ///
 /// Sending an email:
 ///
-/// ```rust
+/// ```rust,no_run
 /// use resend_rs::{
 ///  rate_limit::{send_with_retry, send_with_retry_opts, RetryOptions},
 ///  types::CreateEmailBaseOptions,
@@ -94,9 +583,7 @@ pub async fn send_with_retry_opts<A: Future<Output = Result<B>> + Send, B: Send>
     let res = f().await;
 
     if let Err(Error::RateLimit {
-        ratelimit_limit: _,
-        ratelimit_remaining: _,
-        ratelimit_reset,
+        ratelimit_reset, ..
     }) = res
     {
         // Base case
@@ -114,9 +601,7 @@ pub async fn send_with_retry_opts<A: Future<Output = Result<B>> + Send, B: Send>
             max_retries: opts.max_retries.saturating_sub(1),
         };
 
-        let sleep_millis = ratelimit_reset.map_or(opts.duration_ms, |r| r.saturating_mul(1000));
-        let jitter = rand::rng().random_range(opts.jitter_range_ms.clone());
-        std::thread::sleep(Duration::from_millis(sleep_millis + jitter));
+        futures_timer::Delay::new(opts.delay(ratelimit_reset)).await;
 
         #[cfg(test)]
         {
@@ -153,7 +638,7 @@ pub async fn send_with_retry<A: Future<Output = Result<B>> + Send, B: Send>(
 ///
 /// ## Example
 ///
-/// ```rust
+/// ```rust,no_run
 /// use resend_rs::{
 ///   rate_limit::{send_with_retry_opts, RetryOptions},
 ///   retry,
@@ -182,7 +667,7 @@ macro_rules! retry {
 ///
 /// ## Example
 ///
-/// ```rust
+/// ```rust,no_run
 /// use resend_rs::{
 ///   rate_limit::{send_with_retry_opts, RetryOptions},
 ///   retry_opts,
@@ -206,16 +691,13 @@ macro_rules! retry_opts {
 }
 
 #[cfg(test)]
-#[allow(clippy::needless_return)]
+#[allow(clippy::needless_return, clippy::unwrap_used)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use super::{RetryOptions, send_with_retry_opts};
-    #[cfg(not(feature = "blocking"))]
     use crate::Error;
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn test_retry_count_err() {
         let mut run_count = 0u32;
 
@@ -224,6 +706,7 @@ mod test {
                 ratelimit_limit: Some(10),
                 ratelimit_remaining: Some(10),
                 ratelimit_reset: Some(1),
+                context: Box::default(),
             };
             Result::<(), Error>::Err(err)
         };
@@ -247,9 +730,120 @@ mod test {
         assert_eq!(run_count, 0);
     }
 
+    #[test]
+    fn retry_policy_delay() {
+        use super::RetryPolicy;
+        use std::time::Duration;
+
+        let policy = RetryPolicy::default()
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_secs(1));
+
+        // Exponential with jitter: between half and the full backoff.
+        for (attempt, full) in [
+            (0, 100),
+            (1, 200),
+            (2, 400),
+            (3, 800),
+            (4, 1000),
+            (20, 1000),
+        ] {
+            let delay = policy.delay(attempt, None);
+            assert!(
+                delay >= Duration::from_millis(full / 2),
+                "{attempt}: {delay:?}"
+            );
+            assert!(delay <= Duration::from_millis(full), "{attempt}: {delay:?}");
+        }
+
+        // Server provided delays take precedence, up to the maximum backoff.
+        let delay = policy.delay(0, Some(Duration::from_millis(500)));
+        assert!(delay >= Duration::from_millis(500));
+        assert!(delay < Duration::from_millis(600));
+        let delay = policy.delay(0, Some(Duration::from_secs(2)));
+        assert_eq!(delay, Duration::from_secs(1));
+        let delay = policy.delay(0, Some(Duration::MAX));
+        assert_eq!(delay, Duration::from_secs(1));
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn huge_ratelimit_reset() {
+        use std::time::{Duration, Instant};
+
+        use super::RetryPolicy;
+        use crate::{
+            ConfigBuilder, Resend,
+            test::{ScriptedTransport, json_response},
+        };
+
+        let transport = ScriptedTransport::new(|call, _| {
+            if call > 0 {
+                return Ok(json_response(200, r#"{"has_more":false,"data":[]}"#));
+            }
+            let body = r#"{"statusCode":429,"message":"","name":"rate_limit_exceeded"}"#;
+            let mut response = json_response(429, body);
+            let _old = response
+                .headers_mut()
+                .insert("ratelimit-reset", u64::MAX.into());
+            Ok(response)
+        });
+        let policy = RetryPolicy::default().with_max_backoff(Duration::from_millis(10));
+        let config = ConfigBuilder::new("re_test")
+            .transport(transport.clone())
+            .retry_policy(policy)
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        let start = Instant::now();
+        let _keys = resend
+            .api_keys
+            .list(crate::list_opts::ListOptions::default())
+            .await
+            .unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(transport.calls(), 2);
+
+        let opts = RetryOptions::default();
+        assert_eq!(opts.delay(Some(u64::MAX)), Duration::from_millis(u64::MAX));
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn quota_exceeded_not_retried() {
+        use std::time::Duration;
+
+        use super::RetryPolicy;
+        use crate::{
+            ConfigBuilder, Resend,
+            test::{ScriptedTransport, json_response},
+        };
+
+        let transport = ScriptedTransport::new(|_, _| {
+            let body = r#"{"statusCode":429,"message":"","name":"monthly_quota_exceeded"}"#;
+            let mut response = json_response(429, body);
+            let _old = response
+                .headers_mut()
+                .insert("ratelimit-reset", 3600.into());
+            Ok(response)
+        });
+        let policy = RetryPolicy::default().with_max_backoff(Duration::from_millis(10));
+        let config = ConfigBuilder::new("re_test")
+            .transport(transport.clone())
+            .retry_policy(policy)
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        let res = resend
+            .api_keys
+            .list(crate::list_opts::ListOptions::default())
+            .await;
+        assert!(res.is_err());
+        assert_eq!(transport.calls(), 1);
+    }
+
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn test_retry_count_ok() {
         let mut retry_count = 0u32;
 
@@ -261,4 +855,177 @@ mod test {
         assert!(res.is_ok());
         assert_eq!(retry_count, 0);
     }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn quota_limiter() {
+        use super::{Limiter, Quota, QuotaLimiter};
+        use std::{num::NonZeroU32, time::Duration};
+
+        let quota = Quota::new(NonZeroU32::new(10).unwrap(), Duration::from_secs(1))
+            .with_burst(NonZeroU32::new(2).unwrap());
+        let limiter = QuotaLimiter::new(quota);
+
+        // 2 at once, then one every 100ms.
+        let start = std::time::Instant::now();
+        for _ in 0..4 {
+            limiter.until_ready().await.unwrap();
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(190), "{elapsed:?}");
+        assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn shared_custom_limiter() {
+        use super::Limiter;
+        use crate::{ConfigBuilder, Resend, Result};
+        use std::sync::{
+            Arc,
+            atomic::{AtomicU32, Ordering},
+        };
+
+        /// Counts requests and refuses all of them.
+        #[derive(Debug, Default)]
+        struct Closed(AtomicU32);
+
+        #[async_trait::async_trait]
+        impl Limiter for Closed {
+            async fn until_ready(&self) -> Result<()> {
+                let _previous = self.0.fetch_add(1, Ordering::SeqCst);
+                Err(Error::other("closed"))
+            }
+        }
+
+        let limiter = Arc::new(Closed::default());
+        for _ in 0..2 {
+            let shared: Arc<dyn Limiter> = Arc::<Closed>::clone(&limiter);
+            let config = ConfigBuilder::new("re_test")
+                .base_url("http://127.0.0.1:9".parse().unwrap())
+                .limiter(shared)
+                .build();
+            let res = Resend::with_config(config).emails.get("abc").await;
+            assert!(matches!(res, Err(Error::Other { message, .. }) if message == "closed"));
+        }
+        assert_eq!(limiter.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn rate_limit_headers() {
+        use super::RateLimitHeaders;
+        use reqwest::header::HeaderMap;
+
+        let mut headers = HeaderMap::new();
+        let _old = headers.insert("ratelimit-limit", "10".parse().unwrap());
+        let _old = headers.insert("ratelimit-remaining", "7".parse().unwrap());
+        let _old = headers.insert("ratelimit-reset", "1".parse().unwrap());
+        let _old = headers.insert("ratelimit-policy", "10;w=2".parse().unwrap());
+
+        let parsed = RateLimitHeaders::from_headers(&headers);
+        assert_eq!(parsed.limit, Some(10));
+        assert_eq!(parsed.remaining, Some(7));
+        assert_eq!(parsed.reset, Some(1));
+        assert_eq!(parsed.window, Some(2));
+
+        assert_eq!(
+            RateLimitHeaders::from_headers(&HeaderMap::new()),
+            RateLimitHeaders::default()
+        );
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn adaptive_quota_limiter() {
+        use super::{Limiter, Quota, QuotaLimiter, RateLimitHeaders};
+        use std::{num::NonZeroU32, time::Duration};
+
+        // Stricter limit
+        let limiter = QuotaLimiter::new(Quota::default());
+        limiter.update(&RateLimitHeaders {
+            limit: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(
+            limiter.quota(),
+            Quota::default().with_burst(NonZeroU32::new(2).unwrap())
+        );
+        let quota = Quota::per_second(NonZeroU32::new(10).unwrap());
+        let limiter = QuotaLimiter::new(quota);
+        limiter.update(&RateLimitHeaders {
+            limit: Some(2),
+            window: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(
+            limiter.quota(),
+            Quota::new(NonZeroU32::new(2).unwrap(), Duration::from_millis(1100))
+        );
+
+        // Higher limits never raise the configured quota
+        let limiter = QuotaLimiter::new(quota);
+        limiter.update(&RateLimitHeaders {
+            limit: Some(100),
+            ..Default::default()
+        });
+        assert_eq!(limiter.quota(), quota);
+
+        // Bogus headers don't panic nor stall the client
+        let limiter = QuotaLimiter::new(quota);
+        limiter.update(&RateLimitHeaders {
+            limit: Some(u64::MAX),
+            remaining: Some(u64::MAX),
+            reset: Some(u64::MAX),
+            window: Some(u64::MAX),
+        });
+        limiter.update(&RateLimitHeaders {
+            limit: Some(2),
+            remaining: Some(0),
+            reset: Some(u64::MAX),
+            window: Some(u64::MAX),
+        });
+        assert_eq!(limiter.quota().rate().get(), 2);
+        let paused = limiter.paused_until.lock().unwrap().unwrap();
+        assert!(paused <= std::time::Instant::now() + QuotaLimiter::MAX_PAUSE);
+
+        // Not adaptive
+        let limiter = QuotaLimiter::new(Quota::default()).with_adaptive(false);
+        limiter.update(&RateLimitHeaders {
+            limit: Some(2),
+            remaining: Some(0),
+            reset: Some(1),
+            window: None,
+        });
+        assert_eq!(limiter.quota(), Quota::default());
+        let start = std::time::Instant::now();
+        limiter.until_ready().await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(500));
+
+        // Plenty remaining, not paused
+        let limiter = QuotaLimiter::new(Quota::default());
+        limiter.update(&RateLimitHeaders {
+            limit: Some(10),
+            remaining: Some(5),
+            reset: Some(1),
+            window: Some(1),
+        });
+        assert!(limiter.paused_until.lock().unwrap().is_none());
+
+        // At most 10% remaining, spread over the rest of the window
+        limiter.update(&RateLimitHeaders {
+            limit: Some(10),
+            remaining: Some(1),
+            reset: Some(1),
+            window: Some(1),
+        });
+        assert!(limiter.paused_until.lock().unwrap().is_some());
+
+        // Nothing remaining, paused until the reset
+        let limiter = QuotaLimiter::new(Quota::default());
+        limiter.update(&RateLimitHeaders {
+            remaining: Some(0),
+            reset: Some(1),
+            ..Default::default()
+        });
+        let start = std::time::Instant::now();
+        limiter.until_ready().await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(900));
+    }
 }
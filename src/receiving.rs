@@ -1,15 +1,13 @@
 use std::sync::Arc;
 
 use ::reqwest::Method;
+use macro_rules_attribute::apply;
 use mailparse::{DispositionType, MailHeaderMap};
 
-#[cfg(feature = "blocking")]
-use reqwest::blocking as reqwest;
-
 use crate::{
     Config, Error, Result,
     emails::EmailsSvc,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     receiving::types::ForwardReceivingEmail,
     types::{
         Attachment, CreateAttachment, CreateEmailBaseOptions, ForwardInboundEmailResponse,
@@ -21,17 +19,16 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct ReceivingSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl ReceivingSvc {
     /// Retrieve a single received email.
     ///
     /// <https://resend.com/docs/api-reference/emails/retrieve-received-email>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, email_id: &str, opts: GetInboundEmailOptions) -> Result<InboundEmail> {
         let path = format!("/emails/receiving/{email_id}");
 
         let request = self.0.build(Method::GET, &path).query(&opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<InboundEmail>().await?;
+        let content = self.0.send_json::<InboundEmail>(request).await?;
 
         Ok(content)
     }
@@ -39,28 +36,38 @@ impl ReceivingSvc {
     /// Retrieve a list of received emails for the authenticated user.
     ///
     /// <https://resend.com/docs/api-reference/emails/list-received-emails>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<InboundEmail>> {
         let request = self
             .0
             .build(Method::GET, "/emails/receiving")
             .query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<InboundEmail>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<InboundEmail>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<InboundEmail> {
+        Paginator::new(
+            Arc::clone(&self.0),
+            "/emails/receiving".to_owned(),
+            &list_opts,
+        )
+    }
+
     /// Retrieve a single attachment from a received email.
     ///
     /// <https://resend.com/docs/api-reference/emails/retrieve-received-email>
-    #[maybe_async::maybe_async]
     pub async fn get_attachment(&self, attachment_id: &str, email_id: &str) -> Result<Attachment> {
         let path = format!("/emails/receiving/{email_id}/attachments/{attachment_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Attachment>().await?;
+        let content = self.0.send_json::<Attachment>(request).await?;
 
         Ok(content)
     }
@@ -68,7 +75,6 @@ impl ReceivingSvc {
     /// Retrieve a list of email attachments and their contents.
     ///
     /// <https://resend.com/docs/api-reference/attachments/list-received-email-attachments>
-    #[maybe_async::maybe_async]
     pub async fn list_attachments<T>(
         &self,
         email_id: &str,
@@ -77,13 +83,27 @@ impl ReceivingSvc {
         let path = format!("/emails/receiving/{email_id}/attachments");
 
         let request = self.0.build(Method::GET, &path).query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Attachment>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<Attachment>>(request)
+            .await?;
 
         Ok(content)
     }
 
-    #[maybe_async::maybe_async]
+    /// Retrieves the items of every page of [`Self::list_attachments`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_attachments_all<T>(
+        &self,
+        email_id: &str,
+        list_opts: ListOptions<T>,
+    ) -> Paginator<Attachment> {
+        let path = format!("/emails/receiving/{email_id}/attachments");
+
+        Paginator::new(Arc::clone(&self.0), path, &list_opts)
+    }
+
     pub async fn forward(
         &self,
         opts: ForwardReceivingEmail,
@@ -93,7 +113,7 @@ impl ReceivingSvc {
             .await?;
 
         let raw = email_response.raw.ok_or_else(|| {
-            Error::Resend(crate::types::ErrorResponse {
+            Error::from(crate::types::ErrorResponse {
                 status_code: 400,
                 message: "Raw email content is not available for this email".to_owned(),
                 name: "validation_error".to_owned(),
@@ -105,11 +125,8 @@ impl ReceivingSvc {
         let email_svc = EmailsSvc(Arc::<Config>::clone(&self.0));
 
         if opts.passthrough {
-            let parsed =
-                mailparse::parse_mail(&raw_response_content).map_err(|e| Error::Parse {
-                    message: "Failed to parse raw email".to_owned(),
-                    source: Some(Box::new(e)),
-                })?;
+            let parsed = mailparse::parse_mail(&raw_response_content)
+                .map_err(|e| Error::parse("Failed to parse raw email", e))?;
 
             let attachments = parsed
                 .subparts
@@ -123,11 +140,11 @@ impl ReceivingSvc {
                     let filename = disposition
                         .params
                         .get("filename")
-                        .ok_or_else(|| Error::Other("Could not get filename".to_string()))?
+                        .ok_or_else(|| Error::other("Could not get filename"))?
                         .to_owned();
                     let content = attachment
                         .get_body_raw()
-                        .map_err(|_e| Error::Other("Could not get attachment body".to_string()))?;
+                        .map_err(|_e| Error::other("Could not get attachment body"))?;
                     let content_type = attachment.ctype.mimetype.clone();
 
                     if let Some(content_id) = attachment.headers.get_first_header("Content-ID") {
@@ -171,7 +188,7 @@ impl ReceivingSvc {
                 format!("Fwd: {}", email_response.subject)
             };
 
-            let attachment = CreateAttachment::from_content(raw_response_content.to_vec())
+            let attachment = CreateAttachment::from_content(raw_response_content)
                 .with_filename("forwarded_message.eml")
                 .with_content_type("message/rfc822");
 
@@ -346,7 +363,6 @@ pub mod types {
 #[allow(clippy::unwrap_used)]
 #[allow(clippy::needless_return)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -357,7 +373,6 @@ mod test {
     #[ignore = "At the moment, we can't programmatically send inbound emails and since said inbound emails are only retained for 2 weeks, this cannot be automatically tested."]
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         use crate::types::GetInboundEmailOptions;
 
@@ -0,0 +1,334 @@
+//! Per request options: timeout, deadline, headers, idempotency key, rate limit and retries.
+//!
+//! Every service has a `with_options` method returning a copy of the service that applies
+//! [`RequestOptions`] to the requests it sends, without building a new [`reqwest::Client`]. The
+//! original service and its clones are not affected.
+//!
+//! ### Example
+//!
+//! ```rust,no_run
+//! # async fn run() -> resend_rs::Result<()> {
+//! use std::time::Duration;
+//!
+//! use resend_rs::request_opts::RequestOptions;
+//! use resend_rs::{Resend, types::CreateEmailBaseOptions};
+//!
+//! let resend = Resend::default();
+//! let email = CreateEmailBaseOptions::new("Acme <onboarding@a.dev>", ["delivered@resend.dev"], "Hi")
+//!     .with_text("Hello World!");
+//!
+//! let options = RequestOptions::new()
+//!     .with_timeout(Duration::from_secs(60))
+//!     .with_deadline(Duration::from_mins(2))
+//!     .with_header("x-trace-id".parse().unwrap(), "abc".parse().unwrap())
+//!     .without_retry();
+//! let sent = resend.emails.with_options(options).send(email).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Dropping the future of an async call cancels its request.
+
+use std::{sync::Arc, time::Duration};
+
+use reqwest::Request;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::services::{
+    ApiKeysSvc, AutomationsSvc, BatchSvc, BroadcastsSvc, ContactsSvc, DomainsSvc, EmailsSvc,
+    EventsSvc, LogsSvc, OAuthSvc, ReceivingSvc, SegmentsSvc, SuppressionsSvc, TemplateSvc,
+    TopicsSvc, WebhookSvc,
+};
+
+/// Options applied to every request of a service returned by `with_options`, see the
+/// [module level documentation](self).
+///
+/// Note that [`RequestOptions::default()`] changes nothing.
+#[must_use]
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    timeout: Option<Duration>,
+    deadline: Option<Duration>,
+    headers: HeaderMap,
+    idempotency_key: Option<String>,
+    skip_rate_limit: bool,
+    skip_retry: bool,
+}
+
+impl RequestOptions {
+    /// Creates new [`RequestOptions`] that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the timeout of every attempt, overriding the one of the http client.
+    #[inline]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum duration of every request including its retries and the backoff between
+    /// them, whereas [`RequestOptions::with_timeout`] applies to every attempt.
+    ///
+    /// Retrying stops once the backoff would end after the deadline, and the timeout of the last
+    /// attempt is shortened to end at the deadline.
+    #[inline]
+    pub const fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Adds a header, replacing any header with the same name set by the request.
+    #[inline]
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        let _old = self.headers.insert(name, value);
+        self
+    }
+
+    /// Adds the headers of `headers`, replacing any header with the same name set by the request.
+    #[inline]
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Adds an `Idempotency-Key` header, replacing the one set with
+    /// [`Idempotent`](crate::idempotent::Idempotent) (if any).
+    ///
+    /// The key is only sent with `POST` and `PATCH` requests (and their retries), never with
+    /// reads such as the pages of a `list_all`. An idempotency key identifies a single call, so
+    /// use new options for every idempotent call.
+    #[inline]
+    pub fn with_idempotency_key(mut self, idempotency_key: &str) -> Self {
+        self.idempotency_key = Some(idempotency_key.to_owned());
+        self
+    }
+
+    /// Sends the requests without waiting for the client side rate limit.
+    ///
+    /// The requests still update the rate limit with the headers of their responses.
+    #[inline]
+    pub const fn without_rate_limit(mut self) -> Self {
+        self.skip_rate_limit = true;
+        self
+    }
+
+    /// Does not retry the requests, regardless of the
+    /// [`RetryPolicy`](crate::rate_limit::RetryPolicy) of the client.
+    #[inline]
+    pub const fn without_retry(mut self) -> Self {
+        self.skip_retry = true;
+        self
+    }
+
+    #[inline]
+    pub(crate) const fn skip_rate_limit(&self) -> bool {
+        self.skip_rate_limit
+    }
+
+    #[inline]
+    pub(crate) const fn skip_retry(&self) -> bool {
+        self.skip_retry
+    }
+
+    #[inline]
+    pub(crate) const fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    #[inline]
+    pub(crate) fn idempotency_key(&self) -> Option<&str> {
+        self.idempotency_key.as_deref()
+    }
+
+    /// Applies the timeout and headers to `request`.
+    pub(crate) fn apply(&self, request: &mut Request) {
+        if let Some(timeout) = self.timeout {
+            *request.timeout_mut() = Some(timeout);
+        }
+
+        let headers = request.headers_mut();
+        headers.extend(self.headers.clone());
+
+        // The key added when building the request comes first, before the one of `Idempotent`.
+        let mut keys = headers.get_all(IDEMPOTENCY_KEY).iter();
+        if let (Some(key), Some(_)) = (keys.next(), keys.next()) {
+            let key = key.clone();
+            let _old = headers.insert(IDEMPOTENCY_KEY, key);
+        }
+    }
+}
+
+/// The name of the idempotency key header.
+pub(crate) const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+macro_rules! with_options {
+    ($($svc:ident),* $(,)?) => {
+        $(
+            impl $svc {
+                /// Returns a copy of this service that applies `options` to its requests,
+                /// replacing the [`RequestOptions`] set before (if any).
+                #[must_use]
+                pub fn with_options(&self, options: RequestOptions) -> Self {
+                    Self(Arc::new(self.0.with_options(options)))
+                }
+            }
+        )*
+    };
+}
+
+with_options!(
+    ApiKeysSvc,
+    AutomationsSvc,
+    BatchSvc,
+    BroadcastsSvc,
+    ContactsSvc,
+    DomainsSvc,
+    EmailsSvc,
+    EventsSvc,
+    LogsSvc,
+    OAuthSvc,
+    ReceivingSvc,
+    SegmentsSvc,
+    SuppressionsSvc,
+    TemplateSvc,
+    TopicsSvc,
+    WebhookSvc,
+);
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use crate::test::ScriptedTransport;
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn with_options() {
+        use std::time::Duration;
+
+        use super::RequestOptions;
+        use crate::{ConfigBuilder, Resend, rate_limit::RetryPolicy};
+
+        let requests = ScriptedTransport::unavailable();
+        let config = ConfigBuilder::new("re_test")
+            .transport(requests.clone())
+            .retry_policy(RetryPolicy::default().with_initial_backoff(Duration::from_millis(1)))
+            .build();
+        let resend = Resend::with_config(config);
+
+        let options = RequestOptions::new()
+            .with_timeout(Duration::from_secs(90))
+            .with_header("x-trace-id".parse().unwrap(), "abc".parse().unwrap())
+            .with_idempotency_key("options")
+            .without_retry()
+            .without_rate_limit();
+        let email = crate::types::CreateEmailBaseOptions::new("a@a.dev", ["b@b.dev"], "Hi");
+        let emails = resend.emails.with_options(options);
+        let res = emails
+            .send(email.clone().with_idempotency_key("email"))
+            .await;
+        assert!(res.is_err());
+
+        // The options apply to every call of the service
+        let res = emails.send(email.clone()).await;
+        assert!(res.is_err());
+
+        let sent = std::mem::take(&mut *requests.requests());
+        let [request, second] = sent.as_slice() else {
+            panic!("expected 2 requests, got {sent:?}");
+        };
+        assert_eq!(second.headers()["idempotency-key"], "options");
+        assert_eq!(second.headers()["x-trace-id"], "abc");
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(90)));
+        assert_eq!(request.headers()["x-trace-id"], "abc");
+        let keys: Vec<_> = request
+            .headers()
+            .get_all("idempotency-key")
+            .iter()
+            .collect();
+        assert_eq!(keys, ["options"]);
+
+        // The original service is not affected
+        let res = resend.emails.send(email).await;
+        assert!(res.is_err());
+        let sent = std::mem::take(&mut *requests.requests());
+        // Not retried either, as the email has no idempotency key
+        assert_eq!(sent.len(), 1);
+        let request = sent.first().unwrap();
+        assert_eq!(request.timeout(), None);
+        assert!(!request.headers().contains_key("x-trace-id"));
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn idempotency_key_not_on_reads() {
+        use futures_util::TryStreamExt;
+
+        use super::RequestOptions;
+        use crate::{ConfigBuilder, Resend, list_opts::ListOptions, test::json_response};
+
+        let requests = ScriptedTransport::new(|call, _| {
+            let body = serde_json::json!({
+                "has_more": call == 0,
+                "data": [{ "id": format!("s{call}"), "name": "", "created_at": "" }],
+            });
+            Ok(json_response(200, body.to_string()))
+        });
+        let config = ConfigBuilder::new("re_test")
+            .transport(requests.clone())
+            .without_rate_limit()
+            .build();
+        let resend = Resend::with_config(config);
+
+        let options = RequestOptions::new().with_idempotency_key("pages");
+        let segments: Vec<_> = resend
+            .segments
+            .with_options(options)
+            .list_all(ListOptions::default())
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(segments.len(), 2);
+
+        let sent = std::mem::take(&mut *requests.requests());
+        assert_eq!(sent.len(), 2);
+        assert!(
+            sent.iter()
+                .all(|request| !request.headers().contains_key("idempotency-key"))
+        );
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn deadline() {
+        use std::time::{Duration, Instant};
+
+        use super::RequestOptions;
+        use crate::{ConfigBuilder, Resend, rate_limit::RetryPolicy};
+
+        let requests = ScriptedTransport::unavailable();
+        let policy = RetryPolicy::default()
+            .with_initial_backoff(Duration::from_secs(1))
+            .with_max_backoff(Duration::from_secs(1));
+        let config = ConfigBuilder::new("re_test")
+            .transport(requests.clone())
+            .retry_policy(policy)
+            .build();
+        let resend = Resend::with_config(config);
+
+        // The backoff of at least 500ms ends after the deadline
+        let options = RequestOptions::new().with_deadline(Duration::from_millis(400));
+        let start = Instant::now();
+        let res = resend.emails.with_options(options).get("abc").await;
+        assert!(res.is_err());
+        assert!(start.elapsed() < Duration::from_millis(400));
+
+        let sent = std::mem::take(&mut *requests.requests());
+        assert_eq!(sent.len(), 1);
+        let timeout = sent.first().unwrap().timeout().unwrap();
+        assert!(*timeout <= Duration::from_millis(400));
+
+        // Without a deadline the request is retried
+        let res = resend.emails.get("abc").await;
+        assert!(res.is_err());
+        assert_eq!(requests.calls(), 4);
+    }
+}
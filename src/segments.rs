@@ -1,9 +1,13 @@
 use std::fmt;
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
-use crate::{Config, Result, list_opts::ListOptions};
+use crate::{
+    Config, Result,
+    list_opts::{ListOptions, Paginator},
+};
 use crate::{list_opts::ListResponse, types::Segment};
 
 use self::types::CreateSegmentResponse;
@@ -12,21 +16,23 @@ use self::types::CreateSegmentResponse;
 #[derive(Clone)]
 pub struct SegmentsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl SegmentsSvc {
     /// Create a new segment for contacts to be added to.
     ///
     /// Returns an `id` of a created segment.
     ///
     /// <https://resend.com/docs/api-reference/segments/create-segment>
-    #[maybe_async::maybe_async]
     pub async fn create(&self, name: &str) -> Result<CreateSegmentResponse> {
         let segment = types::CreateSegmentRequest {
             name: name.to_owned(),
         };
 
         let request = self.0.build(Method::POST, "/segments");
-        let response = self.0.send(request.json(&segment)).await?;
-        let content = response.json::<CreateSegmentResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateSegmentResponse>(request.json(&segment))
+            .await?;
 
         Ok(content)
     }
@@ -34,13 +40,11 @@ impl SegmentsSvc {
     /// Retrieve a single segment.
     ///
     /// <https://resend.com/docs/api-reference/segments/get-segment>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, id: &str) -> Result<Segment> {
         let path = format!("/segments/{id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Segment>().await?;
+        let content = self.0.send_json::<Segment>(request).await?;
 
         Ok(content)
     }
@@ -48,13 +52,14 @@ impl SegmentsSvc {
     /// Remove an existing segment.
     ///
     /// <https://resend.com/docs/api-reference/segments/delete-segment>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, id: &str) -> Result<bool> {
         let path = format!("/segments/{id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<types::RemoveSegmentResponse>().await?;
+        let content = self
+            .0
+            .send_json::<types::RemoveSegmentResponse>(request)
+            .await?;
 
         Ok(content.deleted)
     }
@@ -64,14 +69,19 @@ impl SegmentsSvc {
     /// - Default limit: no limit (return everything)
     ///
     /// <https://resend.com/docs/api-reference/segments/list-segments>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Segment>> {
         let request = self.0.build(Method::GET, "/segments").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Segment>>().await?;
+        let content = self.0.send_json::<ListResponse<Segment>>(request).await?;
 
         Ok(content)
     }
+
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Segment> {
+        Paginator::new(Arc::clone(&self.0), "/segments".to_owned(), &list_opts)
+    }
 }
 
 impl fmt::Debug for SegmentsSvc {
@@ -128,7 +138,6 @@ pub mod types {
 #[cfg(test)]
 #[allow(clippy::needless_return)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -136,7 +145,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
         let segment = "test_segments";
@@ -151,6 +159,7 @@ mod test {
         assert_eq!(data.name.as_str(), segment);
 
         // List.
+        let other = resend.segments.create("test_segments_other").await?.id;
         let segments = resend.segments.list(ListOptions::default()).await?;
         let segments_before = segments.len();
         assert!(segments_before > 1);
@@ -158,6 +167,8 @@ mod test {
         // Delete.
         let deleted = resend.segments.delete(&id).await?;
         assert!(deleted);
+        let deleted = resend.segments.delete(&other).await?;
+        assert!(deleted);
 
         Ok(())
     }
@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     suppressions::types::SpecifiedMarker,
     types::{
         AddSuppressionOptions, AddSuppressionResponse, BatchAddSuppressionOptions,
@@ -17,15 +18,17 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct SuppressionsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl SuppressionsSvc {
     /// Add an email address to the suppression list.
     ///
     /// <https://resend.com/docs/api-reference/suppressions/add-suppression>
-    #[maybe_async::maybe_async]
     pub async fn add(&self, opts: AddSuppressionOptions) -> Result<AddSuppressionResponse> {
         let request = self.0.build(Method::POST, "/suppressions");
-        let response = self.0.send(request.json(&opts)).await?;
-        let content = response.json::<AddSuppressionResponse>().await?;
+        let content = self
+            .0
+            .send_json::<AddSuppressionResponse>(request.json(&opts))
+            .await?;
 
         Ok(content)
     }
@@ -33,14 +36,12 @@ impl SuppressionsSvc {
     /// Retrieve a single suppression by ID or email.
     ///
     /// <https://resend.com/docs/api-reference/suppressions/get-suppression>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, id_or_email: &str) -> Result<Suppression> {
         let id_or_email = urlencoding::encode(id_or_email);
         let path = format!("/suppressions/{id_or_email}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Suppression>().await?;
+        let content = self.0.send_json::<Suppression>(request).await?;
 
         Ok(content)
     }
@@ -50,26 +51,35 @@ impl SuppressionsSvc {
     /// - Default limit: 20
     ///
     /// <https://resend.com/docs/api-reference/suppressions/list-suppressions>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Suppression>> {
         let request = self.0.build(Method::GET, "/suppressions").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Suppression>>().await?;
+        let content = self
+            .0
+            .send_json::<ListResponse<Suppression>>(request)
+            .await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Suppression> {
+        Paginator::new(Arc::clone(&self.0), "/suppressions".to_owned(), &list_opts)
+    }
+
     /// Remove a single suppression by ID or email.
     ///
     /// <https://resend.com/docs/api-reference/suppressions/remove-suppression>
-    #[maybe_async::maybe_async]
     pub async fn remove(&self, id_or_email: &str) -> Result<RemoveSuppressionResponse> {
         let id_or_email = urlencoding::encode(id_or_email);
         let path = format!("/suppressions/{id_or_email}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<RemoveSuppressionResponse>().await?;
+        let content = self
+            .0
+            .send_json::<RemoveSuppressionResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -77,14 +87,15 @@ impl SuppressionsSvc {
     /// Add up to 100 email addresses to the suppression list at once.
     ///
     /// <https://resend.com/docs/api-reference/suppressions/add-suppressions>
-    #[maybe_async::maybe_async]
     pub async fn batch_add(
         &self,
         opts: BatchAddSuppressionOptions,
     ) -> Result<BatchAddSuppressionResponse> {
         let request = self.0.build(Method::POST, "/suppressions/batch/add");
-        let response = self.0.send(request.json(&opts)).await?;
-        let content = response.json::<BatchAddSuppressionResponse>().await?;
+        let content = self
+            .0
+            .send_json::<BatchAddSuppressionResponse>(request.json(&opts))
+            .await?;
 
         Ok(content)
     }
@@ -104,15 +115,16 @@ impl SuppressionsSvc {
     /// ```
     ///
     /// <https://resend.com/docs/api-reference/suppressions/remove-suppressions>
-    #[maybe_async::maybe_async]
     #[allow(private_bounds)]
     pub async fn batch_remove<M: SpecifiedMarker>(
         &self,
         ids_or_emails: BatchRemoveSuppressionOptions<M>,
     ) -> Result<BatchRemoveSuppressionsResponse> {
         let request = self.0.build(Method::POST, "/suppressions/batch/remove");
-        let response = self.0.send(request.json(&ids_or_emails)).await?;
-        let content = response.json::<BatchRemoveSuppressionsResponse>().await?;
+        let content = self
+            .0
+            .send_json::<BatchRemoveSuppressionsResponse>(request.json(&ids_or_emails))
+            .await?;
 
         Ok(content)
     }
@@ -294,7 +306,6 @@ pub mod types {
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::test::{CLIENT, DebugResult};
     use crate::{
         list_opts::ListResponse,
@@ -383,7 +394,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         use crate::{
             list_opts::ListOptions,
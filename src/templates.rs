@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::types::{
@@ -9,22 +10,24 @@ use crate::types::{
 };
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
 };
 
 /// `Resend` APIs for `/templates` endpoints.
 #[derive(Clone, Debug)]
 pub struct TemplateSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl TemplateSvc {
     /// Create a new template.
     ///
     /// <https://resend.com/docs/api-reference/templates/create-template>
-    #[maybe_async::maybe_async]
     pub async fn create(&self, template: CreateTemplateOptions) -> Result<CreateTemplateResponse> {
         let request = self.0.build(Method::POST, "/templates");
-        let response = self.0.send(request.json(&template)).await?;
-        let content = response.json::<CreateTemplateResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateTemplateResponse>(request.json(&template))
+            .await?;
 
         Ok(content)
     }
@@ -32,13 +35,11 @@ impl TemplateSvc {
     /// Get a template by ID
     ///
     /// <https://resend.com/docs/api-reference/templates/get-template>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, id_or_alias: &str) -> Result<Template> {
         let path = format!("/templates/{id_or_alias}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Template>().await?;
+        let content = self.0.send_json::<Template>(request).await?;
 
         Ok(content)
     }
@@ -46,7 +47,6 @@ impl TemplateSvc {
     /// Update a template.
     ///
     /// <https://resend.com/docs/api-reference/templates/update-template>
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         id_or_alias: &str,
@@ -55,8 +55,10 @@ impl TemplateSvc {
         let path = format!("/templates/{id_or_alias}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateTemplateResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateTemplateResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -64,13 +66,11 @@ impl TemplateSvc {
     /// Publish a template.
     ///
     /// <https://resend.com/docs/api-reference/templates/publish-template>
-    #[maybe_async::maybe_async]
     pub async fn publish(&self, id_or_alias: &str) -> Result<PublishTemplateResponse> {
         let path = format!("/templates/{id_or_alias}/publish");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<PublishTemplateResponse>().await?;
+        let content = self.0.send_json::<PublishTemplateResponse>(request).await?;
 
         Ok(content)
     }
@@ -78,13 +78,14 @@ impl TemplateSvc {
     /// Duplicate a template.
     ///
     /// <https://resend.com/docs/api-reference/templates/duplicate-template>
-    #[maybe_async::maybe_async]
     pub async fn duplicate(&self, id_or_alias: &str) -> Result<DuplicateTemplateResponse> {
         let path = format!("/templates/{id_or_alias}/duplicate");
 
         let request = self.0.build(Method::POST, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DuplicateTemplateResponse>().await?;
+        let content = self
+            .0
+            .send_json::<DuplicateTemplateResponse>(request)
+            .await?;
 
         Ok(content)
     }
@@ -92,13 +93,11 @@ impl TemplateSvc {
     /// Delete a template.
     ///
     /// <https://resend.com/docs/api-reference/templates/delete-template>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, id_or_alias: &str) -> Result<DeleteTemplateResponse> {
         let path = format!("/templates/{id_or_alias}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DeleteTemplateResponse>().await?;
+        let content = self.0.send_json::<DeleteTemplateResponse>(request).await?;
 
         Ok(content)
     }
@@ -108,14 +107,19 @@ impl TemplateSvc {
     /// - Default limit: 20
     ///
     /// <https://resend.com/docs/api-reference/templates/list-templates>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Template>> {
         let request = self.0.build(Method::GET, "/templates").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Template>>().await?;
+        let content = self.0.send_json::<ListResponse<Template>>(request).await?;
 
         Ok(content)
     }
+
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Template> {
+        Paginator::new(Arc::clone(&self.0), "/templates".to_owned(), &list_opts)
+    }
 }
 
 #[allow(unreachable_pub)]
@@ -454,7 +458,6 @@ pub mod types {
 #[allow(clippy::unwrap_used)]
 #[allow(clippy::needless_return)]
 mod test {
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         test::{CLIENT, DebugResult},
         types::CreateTemplateOptions,
@@ -464,7 +467,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         use crate::{list_opts::ListOptions, types::UpdateTemplateOptions};
 
@@ -0,0 +1,1908 @@
+//! In-process mock of a subset of the Resend API for testing.
+//!
+//! [`MockServer`] is a local HTTP server that emulates the Resend endpoints with in-memory state,
+//! so that a [`Resend`] client can be tested without an API key or network access. The following
+//! resources are supported:
+//!
+//! - `emails` (including batch sending, idempotency keys, attachments and share links)
+//! - `contacts` (including segment membership, topic subscriptions and CSV imports)
+//! - `contact-properties`
+//! - `domains`
+//! - `segments`
+//! - `topics`
+//! - `templates`
+//! - `broadcasts`
+//! - `suppressions`
+//! - `webhooks`
+//! - `api-keys`
+//! - `events`
+//! - `automations` (runs are never started)
+//! - `oauth/grants` (grants can't be created, so the list is always empty)
+//! - `logs` (every request made to the server is logged)
+//!
+//! Requests to any other endpoint return a `404` `not_found` error.
+//!
+//! The server runs on its own background thread, so it can be used from both async and blocking
+//! code, and shuts down when the [`MockServer`] is dropped.
+//!
+//! ### Example
+//!
+//! ```rust
+//! # async fn run() -> resend_rs::Result<()> {
+//! use resend_rs::testing::MockServer;
+//! use resend_rs::types::CreateEmailBaseOptions;
+//!
+//! let server = MockServer::start();
+//! let resend = server.client();
+//!
+//! let email = CreateEmailBaseOptions::new("Acme <acme@a.dev>", ["b@b.dev"], "Hello")
+//!     .with_text("Hello World!");
+//! let id = resend.emails.send(email).await?.id;
+//!
+//! let email = resend.emails.get(&id).await?;
+//! assert_eq!(email.subject, "Hello");
+//! assert_eq!(server.objects("emails").len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+// Reading a missing key of a `serde_json::Value` returns `Value::Null` instead of panicking and all
+// stored objects are JSON objects, so writing to them can't panic either.
+#![allow(clippy::indexing_slicing)]
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use reqwest::Url;
+use serde_json::{Map, Value, json};
+
+use crate::{ConfigBuilder, Resend, mode::new_id};
+
+/// A local HTTP server emulating the Resend API.
+///
+/// See the [module level documentation](self) for more information.
+pub struct MockServer {
+    url: Url,
+    store: Arc<Mutex<Store>>,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Starts a new [`MockServer`] on a random local port.
+    ///
+    /// ### Panics
+    ///
+    /// - Panics if the server could not be bound to a local port.
+    #[must_use]
+    pub fn start() -> Self {
+        let listener =
+            std::net::TcpListener::bind("127.0.0.1:0").expect("should bind to a local port");
+        listener
+            .set_nonblocking(true)
+            .expect("should set the listener to non blocking");
+        let address = listener.local_addr().expect("should have a local address");
+
+        let store = Arc::new(Mutex::new(Store::default()));
+        let app = Router::new()
+            .fallback(handle)
+            .with_state(Arc::clone(&store));
+
+        let (shutdown, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let _handle = std::thread::Builder::new()
+            .name("resend-mock-server".to_owned())
+            .spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("should build the mock server runtime");
+
+                runtime.block_on(async move {
+                    let listener = tokio::net::TcpListener::from_std(listener)
+                        .expect("should convert the listener");
+
+                    tokio::select! {
+                        _ = axum::serve(listener, app) => {}
+                        _ = shutdown_rx => {}
+                    }
+                });
+            })
+            .expect("should spawn the mock server thread");
+
+        let url = Url::parse(&format!("http://{address}")).expect("should be a valid URL");
+
+        Self {
+            url,
+            store,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    /// Returns the base url of the server, to be used with [`ConfigBuilder::base_url`].
+    #[must_use]
+    pub fn url(&self) -> Url {
+        self.url.clone()
+    }
+
+    /// Returns a [`ConfigBuilder`] pointed at this server.
+    ///
+    /// The server doesn't enforce any rate limits, so neither does the client.
+    pub fn config(&self) -> ConfigBuilder {
+        ConfigBuilder::new("re_mock_server")
+            .base_url(self.url())
+            .without_rate_limit()
+    }
+
+    /// Returns a [`Resend`] client pointed at this server.
+    pub fn client(&self) -> Resend {
+        Resend::with_config(self.config().build())
+    }
+
+    /// Returns a snapshot of every stored object of `resource` (e.g. `emails`), newest first.
+    #[must_use]
+    pub fn objects(&self, resource: &str) -> Vec<Value> {
+        lock(&self.store)
+            .collections
+            .get(resource)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Removes all the stored state.
+    pub fn reset(&self) {
+        *lock(&self.store) = Store::default();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+impl fmt::Debug for MockServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockServer")
+            .field("url", &self.url.as_str())
+            .finish_non_exhaustive()
+    }
+}
+
+fn lock(store: &Mutex<Store>) -> MutexGuard<'_, Store> {
+    store.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Status code and JSON body of a response.
+type Reply = (StatusCode, Value);
+
+async fn handle(
+    State(store): State<Arc<Mutex<Store>>>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let path = uri.path().to_owned();
+    let query = parse_query(uri.query());
+
+    let boundary = headers
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("multipart/form-data; boundary="));
+
+    let request_body = match boundary {
+        _ if body.is_empty() => Ok(Value::Null),
+        Some(boundary) => Ok(parse_multipart(&body, boundary)),
+        None => serde_json::from_slice::<Value>(&body),
+    };
+
+    let authorized = headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|key| !key.is_empty());
+
+    let mut store = lock(&store);
+
+    let (status, reply) = match request_body {
+        _ if !authorized => error(
+            StatusCode::UNAUTHORIZED,
+            "missing_api_key",
+            "Missing API key in the authorization header.",
+        ),
+        Err(ref e) => error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "validation_error",
+            e.to_string(),
+        ),
+        Ok(ref body) => {
+            let segments = path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| urlencoding::decode(segment).unwrap_or_else(|_| segment.into()))
+                .collect::<Vec<_>>();
+            let segments = segments.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+
+            store.route(&method, &segments, &query, &headers, body)
+        }
+    };
+
+    if !path.starts_with("/logs") {
+        let log = json!({
+            "object": "log",
+            "id": new_id(),
+            "created_at": now(),
+            "endpoint": path,
+            "method": method.as_str(),
+            "response_status": status.as_u16(),
+            "user_agent": headers.get("user-agent").and_then(|v| v.to_str().ok()),
+            "request_body": request_body.unwrap_or(Value::Null),
+            "response_body": reply,
+        });
+        store.insert("logs", log);
+    }
+    drop(store);
+
+    (status, Json(reply)).into_response()
+}
+
+/// The in-memory state of a [`MockServer`].
+#[derive(Debug, Default)]
+struct Store {
+    /// Stored objects per resource, newest first.
+    collections: HashMap<String, Vec<Value>>,
+    /// Contact id -> segment ids.
+    contact_segments: HashMap<String, Vec<String>>,
+    /// Contact id -> topic id -> subscription.
+    contact_topics: HashMap<String, HashMap<String, Value>>,
+    /// Idempotency key -> (request body, reply).
+    idempotency: HashMap<String, (Value, Reply)>,
+}
+
+impl Store {
+    #[allow(clippy::too_many_lines)] // It's a routing table
+    fn route(
+        &mut self,
+        method: &Method,
+        segments: &[&str],
+        query: &HashMap<String, String>,
+        headers: &HeaderMap,
+        body: &Value,
+    ) -> Reply {
+        match (method.as_str(), segments) {
+            // Emails
+            ("POST", ["emails"]) => self.idempotent(headers, body, |store| store.send_email(body)),
+            ("POST", ["emails", "batch"]) => {
+                let permissive = headers
+                    .get("x-batch-validation")
+                    .is_some_and(|v| v.as_bytes() == b"permissive");
+                self.idempotent(headers, body, |store| store.send_batch(body, permissive))
+            }
+            ("GET", ["emails"]) => self.list("emails", query),
+            ("GET", ["emails", id]) => self.get("emails", id),
+            ("PATCH", ["emails", id]) => self.update("emails", id, body, &["scheduled_at"]),
+            ("POST", ["emails", id, "cancel"]) => self.modify("emails", id, |email| {
+                email["last_event"] = json!("canceled");
+            }),
+            ("POST", ["emails", id, "share"]) => {
+                if self.find("emails", id).is_none() {
+                    return not_found("Email");
+                }
+                if !body["expires_in"].is_null() && share_expiry(&body["expires_in"]).is_none() {
+                    return error(
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        "validation_error",
+                        "The `expires_in` field must be a duration of at most 24 hours.",
+                    );
+                }
+                (
+                    StatusCode::OK,
+                    json!({ "object": "email", "id": id, "url": format!("https://mock.resend.dev/share/{id}") }),
+                )
+            }
+            ("GET", ["emails", id, "attachments"]) => {
+                let Some(email) = self.find("emails", id) else {
+                    return not_found("Email");
+                };
+                let email_id = email["id"].clone();
+                self.list_filtered("attachments", query, |attachment| {
+                    attachment["email_id"] == email_id
+                })
+            }
+            ("GET", ["emails", email, "attachments", id]) => self
+                .find("attachments", id)
+                .filter(|attachment| attachment["email_id"] == *email)
+                .map_or_else(
+                    || not_found("Attachment"),
+                    |attachment| (StatusCode::OK, attachment.clone()),
+                ),
+
+            // Contacts
+            ("POST", ["contacts", "imports"]) => self.create_import(body),
+            ("GET", ["contacts", "imports"]) => self.list("contact_imports", query),
+            ("GET", ["contacts", "imports", id]) => self.get("contact_imports", id),
+            ("POST", ["contacts"]) => self.create_contact(body, None),
+            ("POST", ["audiences", segment, "contacts"]) => {
+                self.create_contact(body, Some(segment))
+            }
+            ("GET", ["contacts"]) => self.list("contacts", query),
+            ("GET", ["audiences", segment, "contacts"]) => {
+                let members = self
+                    .contact_segments
+                    .iter()
+                    .filter(|(_, segments)| segments.iter().any(|s| s == segment))
+                    .map(|(contact, _)| contact.clone())
+                    .collect::<Vec<_>>();
+                self.list_filtered("contacts", query, |contact| {
+                    members.iter().any(|m| contact["id"] == m.as_str())
+                })
+            }
+            ("GET", ["contacts", id]) => self.get("contacts", id),
+            ("PATCH", ["contacts", id]) => self.update(
+                "contacts",
+                id,
+                body,
+                &["first_name", "last_name", "unsubscribed"],
+            ),
+            ("DELETE", ["contacts", id]) => self.delete("contacts", id),
+            ("GET", ["contacts", id, "segments"]) => {
+                let Some(contact) = self.find("contacts", id) else {
+                    return not_found("Contact");
+                };
+                let contact_id = contact["id"].as_str().unwrap_or_default().to_owned();
+                let memberships = self
+                    .contact_segments
+                    .get(&contact_id)
+                    .cloned()
+                    .unwrap_or_default();
+                self.list_filtered("segments", query, |segment| {
+                    memberships.iter().any(|m| segment["id"] == m.as_str())
+                })
+            }
+            ("GET", ["contacts", id, "topics"]) => {
+                let Some(contact) = self.find("contacts", id) else {
+                    return not_found("Contact");
+                };
+                let contact_id = contact["id"].as_str().unwrap_or_default().to_owned();
+                let subscriptions = self
+                    .contact_topics
+                    .get(&contact_id)
+                    .cloned()
+                    .unwrap_or_default();
+                let (status, mut reply) = self.list_filtered("topics", query, |topic| {
+                    topic["id"]
+                        .as_str()
+                        .is_some_and(|topic| subscriptions.contains_key(topic))
+                });
+                for topic in reply["data"].as_array_mut().into_iter().flatten() {
+                    let subscription = topic["id"]
+                        .as_str()
+                        .and_then(|id| subscriptions.get(id))
+                        .cloned()
+                        .unwrap_or_default();
+                    topic["subscription"] = subscription;
+                }
+                (status, reply)
+            }
+            ("PATCH", ["contacts", id, "topics"]) => self.update_contact_topics(id, body),
+            ("POST", ["contacts", id, "segments", segment]) => {
+                self.add_contact_segment(id, segment)
+            }
+            ("DELETE", ["contacts", id, "segments", segment]) => {
+                let Some(contact) = self.find("contacts", id) else {
+                    return not_found("Contact");
+                };
+                let contact_id = contact["id"].as_str().unwrap_or_default().to_owned();
+                let memberships = self.contact_segments.entry(contact_id).or_default();
+                memberships.retain(|s| s != segment);
+                (
+                    StatusCode::OK,
+                    json!({ "object": "contact_segment", "id": segment, "deleted": true }),
+                )
+            }
+
+            // Contact properties
+            ("POST", ["contact-properties"]) => self.create_property(body),
+            ("GET", ["contact-properties"]) => self.list("contact_properties", query),
+            ("GET", ["contact-properties", id]) => self.get("contact_properties", id),
+            ("PATCH", ["contact-properties", id]) => {
+                self.update("contact_properties", id, body, &["fallback_value"])
+            }
+            ("DELETE", ["contact-properties", id]) => self.delete("contact_properties", id),
+
+            // Domains
+            ("POST", ["domains"]) => self.create_domain(body),
+            ("GET", ["domains"]) => self.list("domains", query),
+            ("GET", ["domains", id]) => self.get("domains", id),
+            ("PATCH", ["domains", id]) => self.update(
+                "domains",
+                id,
+                body,
+                &["open_tracking", "click_tracking", "tls"],
+            ),
+            ("DELETE", ["domains", id]) => self.delete("domains", id),
+            ("POST", ["domains", id, "verify"]) => self.modify("domains", id, |domain| {
+                domain["status"] = json!("verified");
+            }),
+
+            // Segments
+            ("POST", ["segments"]) => {
+                let Some(name) = body["name"].as_str() else {
+                    return missing_field("name");
+                };
+                let id = new_id();
+                self.insert(
+                    "segments",
+                    json!({ "object": "segment", "id": id, "name": name, "created_at": now() }),
+                );
+                (
+                    StatusCode::CREATED,
+                    json!({ "object": "segment", "id": id, "name": name }),
+                )
+            }
+            ("GET", ["segments"]) => self.list("segments", query),
+            ("GET", ["segments", id]) => self.get("segments", id),
+            ("DELETE", ["segments", id]) => self.delete("segments", id),
+
+            // Topics
+            ("POST", ["topics"]) => {
+                let (Some(name), Some(default_subscription)) =
+                    (body["name"].as_str(), body["default_subscription"].as_str())
+                else {
+                    return missing_field("name, default_subscription");
+                };
+                let id = new_id();
+                self.insert(
+                    "topics",
+                    json!({
+                        "object": "topic",
+                        "id": id,
+                        "name": name,
+                        "description": body["description"],
+                        "default_subscription": default_subscription,
+                        "visibility": body["visibility"].as_str().unwrap_or("private"),
+                        "created_at": now(),
+                    }),
+                );
+                (StatusCode::CREATED, json!({ "object": "topic", "id": id }))
+            }
+            ("GET", ["topics"]) => self.list("topics", query),
+            ("GET", ["topics", id]) => self.get("topics", id),
+            ("PATCH", ["topics", id]) => {
+                self.update("topics", id, body, &["name", "description", "visibility"])
+            }
+            ("DELETE", ["topics", id]) => self.delete("topics", id),
+
+            // Templates
+            ("POST", ["templates"]) => self.create_template(body),
+            ("GET", ["templates"]) => self.list("templates", query),
+            ("GET", ["templates", id]) => self.get("templates", id),
+            ("PATCH", ["templates", id]) => self.update(
+                "templates",
+                id,
+                body,
+                &[
+                    "name",
+                    "alias",
+                    "from",
+                    "subject",
+                    "reply_to",
+                    "html",
+                    "text",
+                    "variables",
+                ],
+            ),
+            ("POST", ["templates", id, "publish"]) => self.modify("templates", id, |template| {
+                template["status"] = json!("published");
+                template["published_at"] = json!(now());
+            }),
+            ("POST", ["templates", id, "duplicate"]) => {
+                let Some(template) = self.find("templates", id) else {
+                    return not_found("Template");
+                };
+                let mut duplicate = template.clone();
+                let id = new_id();
+                duplicate["id"] = json!(id);
+                duplicate["alias"] = Value::Null;
+                duplicate["status"] = json!("draft");
+                duplicate["published_at"] = Value::Null;
+                duplicate["created_at"] = json!(now());
+                duplicate["updated_at"] = json!(now());
+                self.insert("templates", duplicate);
+                (StatusCode::OK, json!({ "object": "template", "id": id }))
+            }
+            ("DELETE", ["templates", id]) => self.delete("templates", id),
+
+            // Broadcasts
+            ("POST", ["broadcasts"]) => self.create_broadcast(body),
+            ("GET", ["broadcasts"]) => self.list("broadcasts", query),
+            ("GET", ["broadcasts", id]) => self.get("broadcasts", id),
+            ("PATCH", ["broadcasts", id]) => self.update(
+                "broadcasts",
+                id,
+                body,
+                &["from", "subject", "reply_to", "html", "text", "name"],
+            ),
+            ("DELETE", ["broadcasts", id]) => self.delete("broadcasts", id),
+            ("POST", ["broadcasts", id, "send"]) => {
+                let scheduled_at = body["scheduled_at"].clone();
+                self.modify("broadcasts", id, |broadcast| {
+                    send_broadcast(broadcast, scheduled_at);
+                })
+            }
+            ("POST", ["broadcasts", id, "cancel"]) => self.modify("broadcasts", id, |broadcast| {
+                broadcast["status"] = json!("draft");
+                broadcast["scheduled_at"] = Value::Null;
+            }),
+
+            // Suppressions
+            ("POST", ["suppressions"]) => {
+                let Some(email) = body["email"].as_str() else {
+                    return missing_field("email");
+                };
+                let id = self.add_suppression(email);
+                (
+                    StatusCode::CREATED,
+                    json!({ "object": "suppression", "id": id }),
+                )
+            }
+            ("POST", ["suppressions", "batch", "add"]) => {
+                let data = body["emails"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(|email| json!({ "object": "suppression", "id": self.add_suppression(email) }))
+                    .collect::<Vec<_>>();
+                (
+                    StatusCode::CREATED,
+                    json!({ "object": "list", "data": data }),
+                )
+            }
+            ("POST", ["suppressions", "batch", "remove"]) => {
+                let keys = ["emails", "ids"]
+                    .iter()
+                    .filter_map(|key| body[key].as_array())
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>();
+                let data = keys
+                    .iter()
+                    .filter_map(|key| {
+                        let (_, reply) = self.delete("suppressions", key);
+                        reply["deleted"].as_bool().map(|_| reply)
+                    })
+                    .collect::<Vec<_>>();
+                (StatusCode::OK, json!({ "object": "list", "data": data }))
+            }
+            ("GET", ["suppressions"]) => self.list("suppressions", query),
+            ("GET", ["suppressions", id]) => self.get("suppressions", id),
+            ("DELETE", ["suppressions", id]) => self.delete("suppressions", id),
+
+            // Webhooks
+            ("POST", ["webhooks"]) => {
+                let Some(endpoint) = body["endpoint"].as_str() else {
+                    return missing_field("endpoint");
+                };
+                let id = new_id();
+                let signing_secret = format!("whsec_{}", new_id().replace('-', ""));
+                self.insert(
+                    "webhooks",
+                    json!({
+                        "object": "webhook",
+                        "id": id,
+                        "created_at": now(),
+                        "status": "enabled",
+                        "endpoint": endpoint,
+                        "events": body["events"].as_array().cloned().unwrap_or_default(),
+                        "signing_secret": signing_secret,
+                    }),
+                );
+                (
+                    StatusCode::CREATED,
+                    json!({ "object": "webhook", "id": id, "signing_secret": signing_secret }),
+                )
+            }
+            ("GET", ["webhooks"]) => self.list("webhooks", query),
+            ("GET", ["webhooks", id]) => self.get("webhooks", id),
+            ("PATCH", ["webhooks", id]) => {
+                self.update("webhooks", id, body, &["endpoint", "events", "status"])
+            }
+            ("DELETE", ["webhooks", id]) => self.delete("webhooks", id),
+
+            // API keys
+            ("POST", ["api-keys"]) => {
+                let Some(name) = body["name"].as_str() else {
+                    return missing_field("name");
+                };
+                let id = new_id();
+                self.insert(
+                    "api_keys",
+                    json!({
+                        "object": "api_key",
+                        "id": id,
+                        "name": name,
+                        "created_at": now(),
+                        "last_used_at": null,
+                    }),
+                );
+                (
+                    StatusCode::CREATED,
+                    json!({ "id": id, "token": format!("re_{}", new_id().replace('-', "")) }),
+                )
+            }
+            ("GET", ["api-keys"]) => self.list("api_keys", query),
+            ("PATCH", ["api-keys", id]) => self.update("api_keys", id, body, &["name"]),
+            ("DELETE", ["api-keys", id]) => self.delete("api_keys", id),
+
+            // Events
+            ("POST", ["events", "send"]) => {
+                let Some(event) = body["event"].as_str() else {
+                    return missing_field("event");
+                };
+                if self.find("events", &self.event_id(event)).is_none() {
+                    return not_found("Event");
+                }
+                (StatusCode::OK, json!({ "object": "event", "event": event }))
+            }
+            ("POST", ["events"]) => {
+                let Some(name) = body["name"].as_str() else {
+                    return missing_field("name");
+                };
+                if self.find("events", &self.event_id(name)).is_some() {
+                    return error(
+                        StatusCode::CONFLICT,
+                        "validation_error",
+                        "An event with this name already exists.",
+                    );
+                }
+                let id = new_id();
+                self.insert(
+                    "events",
+                    json!({
+                        "object": "event",
+                        "id": id,
+                        "name": name,
+                        "schema": body["schema"],
+                        "created_at": now(),
+                        "updated_at": null,
+                    }),
+                );
+                (StatusCode::CREATED, json!({ "object": "event", "id": id }))
+            }
+            ("GET", ["events"]) => self.list("events", query),
+            ("GET", ["events", key]) => self.get("events", &self.event_id(key)),
+            ("PATCH", ["events", key]) => {
+                let id = self.event_id(key);
+                self.update("events", &id, body, &["schema"])
+            }
+            ("DELETE", ["events", key]) => {
+                let id = self.event_id(key);
+                self.delete("events", &id)
+            }
+
+            // Automations
+            ("POST", ["automations"]) => {
+                let Some(name) = body["name"].as_str() else {
+                    return missing_field("name");
+                };
+                let id = new_id();
+                self.insert(
+                    "automations",
+                    json!({
+                        "object": "automation",
+                        "id": id,
+                        "name": name,
+                        "status": body["status"].as_str().unwrap_or("disabled"),
+                        "created_at": now(),
+                        "updated_at": now(),
+                        "steps": body["steps"].as_array().cloned().unwrap_or_default(),
+                        "connections": body["connections"].as_array().cloned().unwrap_or_default(),
+                    }),
+                );
+                (
+                    StatusCode::CREATED,
+                    json!({ "object": "automation", "id": id }),
+                )
+            }
+            ("GET", ["automations"]) => self.list("automations", query),
+            ("GET", ["automations", id]) => self.get("automations", id),
+            ("PATCH", ["automations", id]) => self.update(
+                "automations",
+                id,
+                body,
+                &["name", "status", "steps", "connections"],
+            ),
+            ("DELETE", ["automations", id]) => self.delete("automations", id),
+            ("POST", ["automations", id, "stop"]) => {
+                let (status, mut reply) = self.modify("automations", id, |automation| {
+                    automation["status"] = json!("disabled");
+                });
+                if status.is_success() {
+                    reply["status"] = json!("disabled");
+                }
+                (status, reply)
+            }
+            ("POST", ["automations", id, "duplicate"]) => {
+                let Some(automation) = self.find("automations", id) else {
+                    return not_found("Automation");
+                };
+                let mut duplicate = automation.clone();
+                let id = new_id();
+                duplicate["id"] = json!(id);
+                duplicate["status"] = json!("disabled");
+                duplicate["created_at"] = json!(now());
+                duplicate["updated_at"] = json!(now());
+                self.insert("automations", duplicate);
+                (StatusCode::OK, json!({ "object": "automation", "id": id }))
+            }
+            ("GET", ["automations", id, "runs"]) => {
+                let Some(automation) = self.find("automations", id) else {
+                    return not_found("Automation");
+                };
+                let automation_id = automation["id"].clone();
+                self.list_filtered("automation_runs", query, |run| {
+                    run["automation_id"] == automation_id
+                })
+            }
+            ("GET", ["automations", _, "runs", id]) => self.get("automation_runs", id),
+
+            // OAuth grants
+            ("GET", ["oauth", "grants"]) => self.list("oauth_grants", query),
+            ("DELETE", ["oauth", "grants", id]) => {
+                let revoked_at = now();
+                let (status, mut reply) = self.modify("oauth_grants", id, |grant| {
+                    grant["revoked_at"] = json!(revoked_at);
+                    grant["revoked_reason"] = json!("revoked_from_api");
+                });
+                if status.is_success() {
+                    reply["revoked_at"] = json!(revoked_at);
+                    reply["revoked_reason"] = json!("revoked_from_api");
+                }
+                (status, reply)
+            }
+
+            // Logs
+            ("GET", ["logs"]) => self.list("logs", query),
+            ("GET", ["logs", id]) => self.get("logs", id),
+
+            _ => error(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "The requested endpoint does not exist.",
+            ),
+        }
+    }
+
+    // ==== Generic CRUD ====
+
+    fn collection(&mut self, resource: &str) -> &mut Vec<Value> {
+        self.collections.entry(resource.to_owned()).or_default()
+    }
+
+    fn insert(&mut self, resource: &str, object: Value) {
+        self.collection(resource).insert(0, object);
+    }
+
+    /// Finds an object by its id, email (contacts, suppressions) or alias (templates).
+    fn find(&self, resource: &str, key: &str) -> Option<&Value> {
+        self.collections
+            .get(resource)?
+            .iter()
+            .find(|o| matches(o, key))
+    }
+
+    fn find_mut(&mut self, resource: &str, key: &str) -> Option<&mut Value> {
+        self.collections
+            .get_mut(resource)?
+            .iter_mut()
+            .find(|o| matches(o, key))
+    }
+
+    fn get(&self, resource: &str, key: &str) -> Reply {
+        self.find(resource, key).map_or_else(
+            || not_found(resource),
+            |object| (StatusCode::OK, object.clone()),
+        )
+    }
+
+    fn list(&self, resource: &str, query: &HashMap<String, String>) -> Reply {
+        self.list_filtered(resource, query, |_| true)
+    }
+
+    fn list_filtered(
+        &self,
+        resource: &str,
+        query: &HashMap<String, String>,
+        filter: impl Fn(&Value) -> bool,
+    ) -> Reply {
+        let items = self
+            .collections
+            .get(resource)
+            .into_iter()
+            .flatten()
+            .filter(|object| filter(object))
+            .collect::<Vec<_>>();
+
+        let position = |id: &String| items.iter().position(|o| o["id"] == id.as_str());
+
+        // Items are stored newest first, `after` moves towards older items.
+        let (start, end) = match (query.get("after"), query.get("before")) {
+            (Some(after), _) => (position(after).map_or(items.len(), |i| i + 1), items.len()),
+            (None, Some(before)) => (0, position(before).unwrap_or(0)),
+            (None, None) => (0, items.len()),
+        };
+        let limit = query
+            .get("limit")
+            .and_then(|limit| limit.parse::<usize>().ok())
+            .unwrap_or(usize::MAX);
+
+        let page = items
+            .get(start..end)
+            .unwrap_or_default()
+            .iter()
+            .take(limit)
+            .map(|&object| object.clone())
+            .collect::<Vec<_>>();
+        let has_more = end.saturating_sub(start) > page.len();
+
+        (
+            StatusCode::OK,
+            json!({ "object": "list", "has_more": has_more, "data": page }),
+        )
+    }
+
+    /// Copies the non null `fields` of `body` into the stored object.
+    fn update(&mut self, resource: &str, key: &str, body: &Value, fields: &[&str]) -> Reply {
+        self.modify(resource, key, |object| {
+            for &field in fields {
+                if let Some(value) = body.get(field).filter(|v| !v.is_null()) {
+                    object[field] = value.clone();
+                }
+            }
+        })
+    }
+
+    fn modify(&mut self, resource: &str, key: &str, f: impl FnOnce(&mut Value)) -> Reply {
+        let Some(object) = self.find_mut(resource, key) else {
+            return not_found(resource);
+        };
+        f(object);
+        if object.get("updated_at").is_some() {
+            object["updated_at"] = json!(now());
+        }
+        (
+            StatusCode::OK,
+            json!({ "object": object["object"], "id": object["id"] }),
+        )
+    }
+
+    fn delete(&mut self, resource: &str, key: &str) -> Reply {
+        let collection = self.collection(resource);
+        let Some(index) = collection.iter().position(|o| matches(o, key)) else {
+            return not_found(resource);
+        };
+        let object = collection.remove(index);
+
+        let mut reply = json!({ "object": object["object"], "id": object["id"], "deleted": true });
+        if resource == "contacts" {
+            reply["contact"] = object["id"].clone();
+        }
+        (StatusCode::OK, reply)
+    }
+
+    // ==== Resource specific ====
+
+    /// Replays the reply of a previous request with the same `Idempotency-Key` header.
+    fn idempotent(
+        &mut self,
+        headers: &HeaderMap,
+        body: &Value,
+        f: impl FnOnce(&mut Self) -> Reply,
+    ) -> Reply {
+        let Some(key) = headers
+            .get("idempotency-key")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned)
+        else {
+            return f(self);
+        };
+
+        match self.idempotency.get(&key) {
+            Some((previous, reply)) if previous == body => reply.clone(),
+            Some(_) => error(
+                StatusCode::CONFLICT,
+                "invalid_idempotent_request",
+                "Same idempotency key used with a different request payload.",
+            ),
+            None => {
+                let reply = f(self);
+                if reply.0.is_success() {
+                    let _old = self.idempotency.insert(key, (body.clone(), reply.clone()));
+                }
+                reply
+            }
+        }
+    }
+
+    fn send_email(&mut self, body: &Value) -> Reply {
+        match validate_email(body).and_then(|email| check_testing_sender(&email).map(|()| email)) {
+            Ok(email) => {
+                let id = email["id"].clone();
+                self.add_attachments(&id, body);
+                self.insert("emails", email);
+                (StatusCode::OK, json!({ "id": id }))
+            }
+            Err(reply) => reply,
+        }
+    }
+
+    fn send_batch(&mut self, body: &Value, permissive: bool) -> Reply {
+        let Some(emails) = body.as_array() else {
+            return error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "validation_error",
+                "Expected an array of emails.",
+            );
+        };
+
+        let mut data = Vec::new();
+        let mut errors = Vec::new();
+        let mut valid = Vec::new();
+
+        for (index, request) in emails.iter().enumerate() {
+            match validate_email(request) {
+                Ok(mut email) => {
+                    // Only the whole batch fails in strict mode, otherwise the email is accepted
+                    // and fails to be delivered, like with the real API.
+                    if let Err(reply) = check_testing_sender(&email) {
+                        if !permissive {
+                            return reply;
+                        }
+                        email["last_event"] = json!("failed");
+                    }
+                    data.push(json!({ "id": email["id"] }));
+                    valid.push((email, request));
+                }
+                Err((status, reply)) if !permissive => return (status, reply),
+                Err((_, reply)) => {
+                    errors.push(json!({ "index": index, "message": reply["message"] }));
+                }
+            }
+        }
+
+        for (email, request) in valid {
+            self.add_attachments(&email["id"], request);
+            self.insert("emails", email);
+        }
+
+        (StatusCode::OK, json!({ "data": data, "errors": errors }))
+    }
+
+    fn create_contact(&mut self, body: &Value, segment: Option<&str>) -> Reply {
+        let Some(email) = body["email"].as_str() else {
+            return missing_field("email");
+        };
+
+        let properties = body["properties"].as_object().map(|properties| {
+            properties
+                .iter()
+                .map(|(key, value)| {
+                    let value = value
+                        .as_str()
+                        .map_or_else(|| value.to_string(), str::to_owned);
+                    (key.clone(), json!({ "value": value, "type": "string" }))
+                })
+                .collect::<Map<_, _>>()
+        });
+
+        let id = new_id();
+        self.insert(
+            "contacts",
+            json!({
+                "object": "contact",
+                "id": id,
+                "email": email,
+                "first_name": body["first_name"],
+                "last_name": body["last_name"],
+                "unsubscribed": body["unsubscribed"].as_bool().unwrap_or(false),
+                "created_at": now(),
+                "properties": properties,
+            }),
+        );
+
+        let segments = body["segments"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|segment| segment["id"].as_str())
+            .chain(segment)
+            .map(str::to_owned)
+            .collect();
+        let _old = self.contact_segments.insert(id.clone(), segments);
+
+        (
+            StatusCode::CREATED,
+            json!({ "object": "contact", "id": id }),
+        )
+    }
+
+    fn add_contact_segment(&mut self, contact: &str, segment: &str) -> Reply {
+        let Some(contact) = self.find("contacts", contact) else {
+            return not_found("Contact");
+        };
+        if self.find("segments", segment).is_none() {
+            return not_found("Segment");
+        }
+
+        let contact_id = contact["id"].as_str().unwrap_or_default().to_owned();
+        let memberships = self.contact_segments.entry(contact_id).or_default();
+        if !memberships.iter().any(|s| s == segment) {
+            memberships.push(segment.to_owned());
+        }
+
+        (StatusCode::OK, json!({ "id": segment }))
+    }
+
+    fn update_contact_topics(&mut self, contact: &str, body: &Value) -> Reply {
+        let Some(contact) = self.find("contacts", contact) else {
+            return not_found("Contact");
+        };
+        let Some(topics) = body.as_array() else {
+            return error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "validation_error",
+                "Expected an array of topics.",
+            );
+        };
+        if let Some(topic) = topics
+            .iter()
+            .filter_map(|topic| topic["id"].as_str())
+            .find(|&topic| self.find("topics", topic).is_none())
+        {
+            return not_found(&format!("Topic {topic}"));
+        }
+
+        let contact_id = contact["id"].as_str().unwrap_or_default().to_owned();
+        let subscriptions = self.contact_topics.entry(contact_id.clone()).or_default();
+        for topic in topics {
+            if let Some(id) = topic["id"].as_str() {
+                let _old = subscriptions.insert(id.to_owned(), topic["subscription"].clone());
+            }
+        }
+
+        (StatusCode::OK, json!({ "id": contact_id }))
+    }
+
+    fn create_property(&mut self, body: &Value) -> Reply {
+        let (Some(key), Some(r#type)) = (body["key"].as_str(), body["type"].as_str()) else {
+            return missing_field("key, type");
+        };
+        if self
+            .collections
+            .get("contact_properties")
+            .into_iter()
+            .flatten()
+            .any(|property| property["key"] == key)
+        {
+            return error(
+                StatusCode::CONFLICT,
+                "validation_error",
+                "A contact property with this key already exists.",
+            );
+        }
+
+        let id = new_id();
+        self.insert(
+            "contact_properties",
+            json!({
+                "object": "contact_property",
+                "id": id,
+                "key": key,
+                "type": r#type,
+                "fallback_value": body["fallback_value"],
+                "created_at": now(),
+            }),
+        );
+
+        (
+            StatusCode::CREATED,
+            json!({ "object": "contact_property", "id": id }),
+        )
+    }
+
+    /// Creates a contact for every row of the CSV `file` of a multipart request whose email isn't
+    /// a contact yet, and updates the existing ones if `on_conflict` is `upsert`.
+    fn create_import(&mut self, body: &Value) -> Reply {
+        let Some(file) = body["file"].as_str() else {
+            return missing_field("file");
+        };
+        // Fields other than the file are sent as JSON encoded text, or as is for strings.
+        let field = |name: &str| {
+            body[name].as_str().map_or(Value::Null, |value| {
+                serde_json::from_str::<Value>(value).unwrap_or_else(|_| json!(value))
+            })
+        };
+        let column_map = field("column_map");
+        let segments = field("segments");
+        let upsert = field("on_conflict") == "upsert";
+
+        let mut rows = file.lines().filter(|line| !line.trim().is_empty());
+        let header = rows
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        let column = |name: &str| header.iter().position(|&column| column == name);
+        let mapped = |field: &str| column(column_map[field].as_str().unwrap_or(field));
+        let Some(email_column) = mapped("email") else {
+            return missing_field("email column");
+        };
+        let first_name_column = mapped("first_name");
+        let last_name_column = mapped("last_name");
+
+        let properties = self.import_properties(&column_map, column);
+
+        let (mut total, mut created, mut updated, mut skipped) = (0, 0, 0, 0);
+        for row in rows {
+            total += 1;
+            let values = row.split(',').map(str::trim).collect::<Vec<_>>();
+            let value = |column: Option<usize>| column.and_then(|i| values.get(i)).copied();
+            let row_properties = properties
+                .iter()
+                .filter_map(|(key, column, r#type)| {
+                    let value = value(*column)?;
+                    Some((key.clone(), json!({ "value": value, "type": r#type })))
+                })
+                .collect::<Map<_, _>>();
+
+            let Some(email) = value(Some(email_column)).filter(|email| !email.is_empty()) else {
+                skipped += 1;
+                continue;
+            };
+            match self.find_mut("contacts", email) {
+                None => {
+                    let contact = json!({
+                        "email": email,
+                        "first_name": value(first_name_column),
+                        "last_name": value(last_name_column),
+                        "segments": segments,
+                    });
+                    let (_, reply) = self.create_contact(&contact, None);
+                    if let Some(contact) = reply["id"]
+                        .as_str()
+                        .and_then(|id| self.find_mut("contacts", id))
+                    {
+                        contact["properties"] = row_properties.into();
+                    }
+                    created += 1;
+                }
+                Some(contact) if upsert => {
+                    if let Some(first_name) = value(first_name_column) {
+                        contact["first_name"] = json!(first_name);
+                    }
+                    if let Some(last_name) = value(last_name_column) {
+                        contact["last_name"] = json!(last_name);
+                    }
+                    if !contact["properties"].is_object() {
+                        contact["properties"] = json!({});
+                    }
+                    for (key, value) in row_properties {
+                        contact["properties"][key] = value;
+                    }
+                    updated += 1;
+                }
+                Some(_) => skipped += 1,
+            }
+        }
+
+        let id = new_id();
+        self.insert(
+            "contact_imports",
+            json!({
+                "object": "contact_import",
+                "id": id,
+                "status": "completed",
+                "created_at": now(),
+                "completed_at": now(),
+                "counts": {
+                    "total": total,
+                    "created": created,
+                    "updated": updated,
+                    "skipped": skipped,
+                    "failed": 0,
+                },
+            }),
+        );
+
+        (
+            StatusCode::CREATED,
+            json!({ "object": "contact_import", "id": id }),
+        )
+    }
+
+    /// Creates the missing contact properties of an import's `column_map` and returns their key,
+    /// column and type.
+    fn import_properties(
+        &mut self,
+        column_map: &Value,
+        column: impl Fn(&str) -> Option<usize>,
+    ) -> Vec<(String, Option<usize>, String)> {
+        let mut properties = Vec::new();
+        for (key, mapping) in column_map["properties"].as_object().into_iter().flatten() {
+            let r#type = mapping["type"].as_str().unwrap_or("string");
+            if !self
+                .collections
+                .get("contact_properties")
+                .into_iter()
+                .flatten()
+                .any(|property| property["key"] == key.as_str())
+            {
+                let _reply = self.create_property(&json!({ "key": key, "type": r#type }));
+            }
+            let column = mapping["column"].as_str().and_then(&column);
+            properties.push((key.clone(), column, r#type.to_owned()));
+        }
+        properties
+    }
+
+    fn create_domain(&mut self, body: &Value) -> Reply {
+        let Some(name) = body["name"].as_str() else {
+            return missing_field("name");
+        };
+
+        let domain = json!({
+            "object": "domain",
+            "id": new_id(),
+            "name": name,
+            "status": "not_started",
+            "created_at": now(),
+            "region": body["region"].as_str().unwrap_or("us-east-1"),
+            "records": [],
+            "capabilities": { "sending": "enabled", "receiving": "disabled" },
+            "open_tracking": body["open_tracking"].as_bool().unwrap_or(false),
+            "click_tracking": body["click_tracking"].as_bool().unwrap_or(false),
+        });
+        self.insert("domains", domain.clone());
+
+        (StatusCode::CREATED, domain)
+    }
+
+    fn create_template(&mut self, body: &Value) -> Reply {
+        let (Some(name), Some(html)) = (body["name"].as_str(), body["html"].as_str()) else {
+            return missing_field("name, html");
+        };
+        if let Some(alias) = body["alias"].as_str()
+            && self.find("templates", alias).is_some()
+        {
+            return error(
+                StatusCode::CONFLICT,
+                "validation_error",
+                "A template with this alias already exists.",
+            );
+        }
+
+        let id = new_id();
+        self.insert(
+            "templates",
+            json!({
+                "object": "template",
+                "id": id,
+                "alias": body["alias"],
+                "name": name,
+                "created_at": now(),
+                "updated_at": now(),
+                "status": "draft",
+                "published_at": null,
+                "from": body["from"],
+                "subject": body["subject"],
+                "reply_to": body["reply_to"],
+                "html": html,
+                "text": body["text"],
+                "variables": body["variables"].as_array().cloned().unwrap_or_default(),
+            }),
+        );
+
+        (
+            StatusCode::CREATED,
+            json!({ "object": "template", "id": id }),
+        )
+    }
+
+    fn create_broadcast(&mut self, body: &Value) -> Reply {
+        let audience_id = body["audience_id"]
+            .as_str()
+            .or_else(|| body["segment_id"].as_str());
+        let (Some(audience_id), Some(from), Some(subject)) =
+            (audience_id, body["from"].as_str(), body["subject"].as_str())
+        else {
+            return missing_field("audience_id, from, subject");
+        };
+
+        let id = new_id();
+        let mut broadcast = json!({
+            "object": "broadcast",
+            "id": id,
+            "name": body["name"].as_str().unwrap_or_default(),
+            "audience_id": audience_id,
+            "status": "draft",
+            "created_at": now(),
+            "scheduled_at": null,
+            "sent_at": null,
+            "from": from,
+            "subject": subject,
+            "reply_to": body["reply_to"],
+            "preview_text": null,
+            "text": body["text"],
+            "html": body["html"],
+        });
+        if body["send"].as_bool() == Some(true) {
+            send_broadcast(&mut broadcast, body["scheduled_at"].clone());
+        }
+        self.insert("broadcasts", broadcast);
+
+        (
+            StatusCode::CREATED,
+            json!({ "object": "broadcast", "id": id }),
+        )
+    }
+
+    /// Adds `email` to the suppression list (if not already there) and returns its id.
+    fn add_suppression(&mut self, email: &str) -> Value {
+        if let Some(suppression) = self.find("suppressions", email) {
+            return suppression["id"].clone();
+        }
+
+        let id = json!(new_id());
+        self.insert(
+            "suppressions",
+            json!({
+                "object": "suppression",
+                "id": id,
+                "email": email,
+                "created_at": now(),
+                "origin": "manual",
+                "source_id": null,
+            }),
+        );
+        id
+    }
+
+    /// Stores the attachments of a sent `email` request, so they can be listed afterwards.
+    fn add_attachments(&mut self, email_id: &Value, email: &Value) {
+        for attachment in email["attachments"].as_array().into_iter().flatten() {
+            let filename = attachment["filename"].as_str();
+            let content_type = attachment["contentType"].as_str().map_or_else(
+                || {
+                    filename
+                        .and_then(|filename| mime_guess::from_path(filename).first())
+                        .map_or_else(
+                            || "application/octet-stream".to_owned(),
+                            |mime| mime.to_string(),
+                        )
+                },
+                str::to_owned,
+            );
+            let size = attachment["content"]
+                .as_str()
+                .and_then(|content| STANDARD.decode(content).ok())
+                .map_or(0, |content| content.len());
+            let disposition = if attachment["content_id"].is_string() {
+                "inline"
+            } else {
+                "attachment"
+            };
+
+            let id = new_id();
+            self.insert(
+                "attachments",
+                json!({
+                    "object": "attachment",
+                    "id": id,
+                    "email_id": email_id,
+                    "filename": filename,
+                    "size": size,
+                    "content_type": content_type,
+                    "content_disposition": disposition,
+                    "content_id": attachment["content_id"],
+                    "download_url": format!("https://mock.resend.dev/attachments/{id}"),
+                    "expires_at": now(),
+                }),
+            );
+        }
+    }
+
+    /// Resolves an event name to the id of the event, other keys are returned as is.
+    fn event_id(&self, key: &str) -> String {
+        self.collections
+            .get("events")
+            .into_iter()
+            .flatten()
+            .find(|event| event["name"] == key)
+            .and_then(|event| event["id"].as_str())
+            .unwrap_or(key)
+            .to_owned()
+    }
+}
+
+/// Validates a single email request and turns it into a stored email.
+fn validate_email(body: &Value) -> Result<Value, Reply> {
+    let (Some(from), Some(to), Some(subject)) = (
+        body["from"].as_str(),
+        body["to"].as_array(),
+        body["subject"].as_str(),
+    ) else {
+        return Err(missing_field("from, to, subject"));
+    };
+
+    if to.is_empty() || to.len() > 50 {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "validation_error",
+            "The `to` field must contain between 1 and 50 recipients.",
+        ));
+    }
+
+    let scheduled = body["scheduled_at"].is_string();
+
+    Ok(json!({
+        "object": "email",
+        "id": new_id(),
+        "message_id": format!("<{}@mock.resend.dev>", new_id()),
+        "from": from,
+        "to": to,
+        "subject": subject,
+        "created_at": now(),
+        "html": body["html"],
+        "text": body["text"],
+        "bcc": body["bcc"],
+        "cc": body["cc"],
+        "reply_to": body["reply_to"],
+        "last_event": if scheduled { "scheduled" } else { "delivered" },
+        "scheduled_at": body["scheduled_at"],
+        "tags": body["tags"],
+    }))
+}
+
+/// Rejects emails from the `resend.dev` testing domain to addresses outside of it.
+fn check_testing_sender(email: &Value) -> Result<(), Reply> {
+    let address = |value: &Value| {
+        let value = value.as_str().unwrap_or_default();
+        value
+            .rsplit_once('<')
+            .map_or(value, |(_, address)| address.trim_end_matches('>'))
+            .to_ascii_lowercase()
+    };
+    let testing = |value: &Value| address(value).ends_with("@resend.dev");
+
+    let mut to = email["to"].as_array().into_iter().flatten();
+    if testing(&email["from"]) && !to.all(testing) {
+        return Err(error(
+            StatusCode::FORBIDDEN,
+            "validation_error",
+            "You can only send testing emails to your own email address or resend.dev addresses.",
+        ));
+    }
+    Ok(())
+}
+
+/// Parses the `expires_in` duration of a share link (e.g. `10m`), up to 24 hours, into seconds.
+fn share_expiry(expires_in: &Value) -> Option<u64> {
+    let expires_in = expires_in.as_str()?;
+    let unit = expires_in.chars().last()?;
+    let amount = expires_in
+        .strip_suffix(unit)?
+        .parse::<u64>()
+        .ok()
+        .filter(|&amount| amount > 0)?;
+    let secs = match unit {
+        's' => amount,
+        'm' => amount.checked_mul(60)?,
+        'h' => amount.checked_mul(3600)?,
+        _ => return None,
+    };
+    (secs <= 86_400).then_some(secs)
+}
+
+/// Parses a `multipart/form-data` body into an object of its text fields.
+fn parse_multipart(body: &[u8], boundary: &str) -> Value {
+    let body = String::from_utf8_lossy(body);
+    let delimiter = format!("--{boundary}");
+
+    body.split(delimiter.as_str())
+        .filter_map(|part| {
+            let (headers, content) = part.split_once("\r\n\r\n")?;
+            let name = headers
+                .split("name=\"")
+                .nth(1)?
+                .split('"')
+                .next()?
+                .to_owned();
+            let content = content.strip_suffix("\r\n").unwrap_or(content);
+            Some((name, Value::String(content.to_owned())))
+        })
+        .collect::<Map<_, _>>()
+        .into()
+}
+
+fn send_broadcast(broadcast: &mut Value, scheduled_at: Value) {
+    if scheduled_at.is_string() {
+        broadcast["status"] = json!("scheduled");
+        broadcast["scheduled_at"] = scheduled_at;
+    } else {
+        broadcast["status"] = json!("sent");
+        broadcast["sent_at"] = json!(now());
+    }
+}
+
+fn matches(object: &Value, key: &str) -> bool {
+    ["id", "email", "alias"]
+        .iter()
+        .any(|field| object.get(field).and_then(Value::as_str) == Some(key))
+}
+
+fn error(status: StatusCode, name: &str, message: impl Into<String>) -> Reply {
+    (
+        status,
+        json!({ "statusCode": status.as_u16(), "message": message.into(), "name": name }),
+    )
+}
+
+fn not_found(resource: &str) -> Reply {
+    error(
+        StatusCode::NOT_FOUND,
+        "not_found",
+        format!("{resource} not found"),
+    )
+}
+
+fn missing_field(fields: &str) -> Reply {
+    error(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "missing_required_field",
+        format!("Missing required fields: {fields}"),
+    )
+}
+
+fn parse_query(query: Option<&str>) -> HashMap<String, String> {
+    query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| {
+            let value = urlencoding::decode(value).map_or_else(|_| value.to_owned(), Into::into);
+            (key.to_owned(), value)
+        })
+        .collect()
+}
+
+/// The current time as an ISO 8601 timestamp.
+fn now() -> String {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let days = i64::try_from(secs / 86_400).unwrap_or_default();
+    let rem = secs % 86_400;
+
+    // <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60,
+        since_epoch.subsec_millis(),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::needless_return, clippy::unwrap_used)]
+mod test {
+    use futures_util::TryStreamExt;
+
+    use crate::{
+        Error,
+        list_opts::ListOptions,
+        test::DebugResult,
+        testing::MockServer,
+        types::{
+            AddSuppressionOptions, AutomationStatus, ContactChanges, ContactIdOrEmail,
+            CreateApiKeyOptions, CreateAttachment, CreateAutomationOptions, CreateBroadcastOptions,
+            CreateContactImportOptions, CreateContactOptions, CreateContactPropertyOptions,
+            CreateDomainOptions, CreateEmailBaseOptions, CreateEventOptions, CreateTemplateOptions,
+            CreateTopicOptions, CreateWebhookOptions, EmailEvent, PropertyType,
+            SendBroadcastOptions, SendEventOptions, ShareEmailOptions, SubscriptionType,
+            UpdateContactTopicOptions,
+        },
+    };
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn emails() -> DebugResult<()> {
+        let server = MockServer::start();
+        let resend = server.client();
+
+        let email = CreateEmailBaseOptions::new("Acme <acme@a.dev>", ["b@b.dev"], "Hello")
+            .with_text("Hello World!");
+
+        let id = resend.emails.send(email.clone()).await?.id;
+        let fetched = resend.emails.get(&id).await?;
+        assert_eq!(fetched.subject, "Hello");
+        assert_eq!(fetched.last_event, EmailEvent::Delivered);
+
+        // Idempotency
+        let first = resend
+            .emails
+            .send(email.clone().with_idempotency_key("key"))
+            .await?;
+        let second = resend
+            .emails
+            .send(email.clone().with_idempotency_key("key"))
+            .await?;
+        assert_eq!(first.id, second.id);
+
+        // Batch
+        let sent = resend.batch.send(vec![email.clone(), email]).await?;
+        assert_eq!(sent.len(), 2);
+
+        // Pagination
+        let page = resend
+            .emails
+            .list(ListOptions::default().with_limit(2))
+            .await?;
+        assert_eq!(page.len(), 2);
+        assert!(page.has_more);
+        let rest = resend
+            .emails
+            .list(ListOptions::default().list_after(&page.data.last().unwrap().id))
+            .await?;
+        assert_eq!(rest.len(), 2);
+        assert!(!rest.has_more);
+        let all: Vec<_> = resend
+            .emails
+            .list_all(ListOptions::default().with_limit(1))
+            .try_collect()
+            .await?;
+        assert_eq!(all.len(), 4);
+        assert_eq!(
+            all.last().map(|email| &email.id),
+            rest.data.last().map(|email| &email.id)
+        );
+
+        // Errors
+        let missing = resend.emails.get("missing").await.unwrap_err();
+        assert!(matches!(&missing, Error::Resend { response, .. } if response.status_code == 404));
+        assert_eq!(missing.context().method, Some(reqwest::Method::GET));
+        assert_eq!(
+            missing.context().endpoint.as_deref(),
+            Some("/emails/missing")
+        );
+        assert!(!missing.is_retryable());
+
+        // Logs
+        let logs = resend.logs.list(ListOptions::default()).await?;
+        assert!(logs.len() >= 7);
+        let _log = resend.logs.get(&logs[0].id).await?;
+
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn resources() -> DebugResult<()> {
+        let server = MockServer::start();
+        let resend = server.client();
+
+        // Segments & contacts
+        let segment = resend.segments.create("Newsletter").await?.id;
+        let contact = CreateContactOptions::new("steve@a.dev").with_first_name("Steve");
+        let contact_id = resend.contacts.create(contact).await?;
+        let _added = resend
+            .contacts
+            .add_contact_segment(&contact_id, &segment)
+            .await?;
+        let members = resend
+            .contacts
+            .list(&segment, ListOptions::default())
+            .await?;
+        assert_eq!(members.len(), 1);
+
+        let changes = ContactChanges::new().with_unsubscribed(true);
+        let _updated = resend.contacts.update("steve@a.dev", changes).await?;
+        assert!(resend.contacts.get(&contact_id).await?.unsubscribed);
+        assert!(resend.contacts.delete(&contact_id).await?);
+
+        // Domains
+        let domain = resend
+            .domains
+            .create(CreateDomainOptions::new("a.dev"))
+            .await?;
+        let _verified = resend.domains.verify(&domain.id).await?;
+        let _deleted = resend.domains.delete(&domain.id).await?;
+
+        // Templates
+        let template = CreateTemplateOptions::new("welcome", "<p>Hi</p>").with_alias("welcome");
+        let template = resend.templates.create(template).await?;
+        let _published = resend.templates.publish("welcome").await?;
+        let fetched = resend.templates.get(&template.id).await?;
+        assert!(fetched.published_at.is_some());
+        let _duplicate = resend.templates.duplicate("welcome").await?;
+        assert_eq!(
+            resend.templates.list(ListOptions::default()).await?.len(),
+            2
+        );
+
+        // Broadcasts
+        let broadcast = CreateBroadcastOptions::new(&segment, "acme@a.dev", "News");
+        let broadcast = resend.broadcasts.create(broadcast).await?.id;
+        let _sent = resend
+            .broadcasts
+            .send(SendBroadcastOptions::new(&broadcast))
+            .await?;
+        assert_eq!(resend.broadcasts.get(&broadcast).await?.status, "sent");
+
+        // Suppressions
+        let _added = resend
+            .suppressions
+            .add(AddSuppressionOptions::new().with_email("bounced@a.dev"))
+            .await?;
+        let suppression = resend.suppressions.get("bounced@a.dev").await?;
+        let removed = resend.suppressions.remove(&suppression.id).await?;
+        assert!(removed.deleted);
+
+        // Webhooks
+        let webhook = CreateWebhookOptions::new(
+            "https://a.dev/webhook",
+            [crate::events::EmailEventType::EmailSent],
+        );
+        let webhook = resend.webhooks.create(webhook).await?;
+        assert_eq!(resend.webhooks.get(&webhook.id).await?.events.len(), 1);
+        assert!(resend.webhooks.delete(&webhook.id).await?);
+
+        assert!(server.objects("webhooks").is_empty());
+        server.reset();
+        assert!(server.objects("segments").is_empty());
+
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn more_resources() -> DebugResult<()> {
+        let server = MockServer::start();
+        let resend = server.client();
+
+        // Attachments & share links
+        let email = CreateEmailBaseOptions::new("Acme <acme@a.dev>", ["b@b.dev"], "Report")
+            .with_attachment(
+                CreateAttachment::from_content(b"a,b\n".to_vec()).with_filename("report.csv"),
+            );
+        let email = resend.emails.send(email).await?.id;
+        let attachments = resend
+            .emails
+            .list_attachments(&email, ListOptions::default())
+            .await?;
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments.data[0].size, 4);
+        assert_eq!(attachments.data[0].content_type, "text/csv");
+        let _attachment = resend
+            .emails
+            .get_attachment(&email, &attachments.data[0].id)
+            .await?;
+        let options = ShareEmailOptions::new().with_expires_in("10m");
+        assert_eq!(resend.emails.share(&email, options).await?.id, email);
+        let options = ShareEmailOptions::new().with_expires_in("72h");
+        assert!(resend.emails.share(&email, options).await.is_err());
+
+        // Topics & contact properties
+        let topic = CreateTopicOptions::new("Product updates", SubscriptionType::OptOut);
+        let topic = resend.topics.create(topic).await?.id;
+        let property = CreateContactPropertyOptions::new("plan", PropertyType::String);
+        let property = resend.contacts.create_property(property).await?.id;
+        assert_eq!(resend.contacts.get_property(&property).await?.key, "plan");
+
+        let contact = CreateContactOptions::new("steve@a.dev");
+        let _contact = resend.contacts.create(contact).await?;
+        let subscription = UpdateContactTopicOptions::new(topic.as_ref(), SubscriptionType::OptIn);
+        let _updated = resend
+            .contacts
+            .update_contact_topics("steve@a.dev", [subscription])
+            .await?;
+        let topics = resend
+            .contacts
+            .get_contact_topics("steve@a.dev", ListOptions::default())
+            .await?;
+        assert_eq!(topics.len(), 1);
+        assert_eq!(topics.data[0].subscription, SubscriptionType::OptIn);
+
+        // Contact imports
+        let path = std::env::temp_dir().join("resend-rs-mock-import.csv");
+        std::fs::write(
+            &path,
+            "email,first_name\nsteve@a.dev,Steve\nbill@a.dev,Bill\n",
+        )
+        .unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let import = resend
+            .contacts
+            .create_import(file, CreateContactImportOptions::new())
+            .await?;
+        let _import = resend.contacts.get_import(&import.id).await?;
+        assert_eq!(server.objects("contacts").len(), 2);
+        let _removed = std::fs::remove_file(path);
+
+        // API keys
+        let key = resend
+            .api_keys
+            .create(CreateApiKeyOptions::new("ci"))
+            .await?;
+        assert_eq!(resend.api_keys.list(ListOptions::default()).await?.len(), 1);
+        resend.api_keys.delete(&key.id).await?;
+
+        // Events
+        let event = CreateEventOptions {
+            name: "signed_up".to_owned(),
+            schema: serde_json::json!({}),
+        };
+        let event = resend.events.create(event).await?.id;
+        assert_eq!(resend.events.get("signed_up").await?.id, event);
+        let sent = SendEventOptions {
+            event: "signed_up".to_owned(),
+            contact_id_or_email: ContactIdOrEmail::Email("steve@a.dev".to_owned()),
+            payload: serde_json::json!({}),
+        };
+        let _sent = resend.events.send(sent).await?;
+        let _deleted = resend.events.delete("signed_up").await?;
+
+        // Automations
+        let automation = CreateAutomationOptions {
+            name: "Welcome".to_owned(),
+            status: AutomationStatus::Enabled,
+            steps: Vec::new(),
+            connections: Vec::new(),
+        };
+        let automation = resend.automations.create(automation).await?.id;
+        let stopped = resend.automations.stop(&automation).await?;
+        assert!(matches!(stopped.status, AutomationStatus::Disabled));
+        let _duplicate = resend.automations.duplicate(&automation).await?;
+        assert_eq!(
+            resend.automations.list(ListOptions::default()).await?.len(),
+            2
+        );
+
+        // OAuth grants
+        assert!(
+            resend
+                .oauth
+                .list(ListOptions::default())
+                .await?
+                .data
+                .is_empty()
+        );
+
+        Ok(())
+    }
+}
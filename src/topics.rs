@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::{
         CreateTopicOptions, CreateTopicResponse, DeleteTopicResponse, Topic, UpdateTopicOptions,
         UpdateTopicResponse,
@@ -15,15 +16,17 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct TopicsSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl TopicsSvc {
     /// Create and email topics to segment your audience.
     ///
     /// <https://resend.com/docs/api-reference/topics/create-topic>
-    #[maybe_async::maybe_async]
     pub async fn create(&self, topic: CreateTopicOptions) -> Result<CreateTopicResponse> {
         let request = self.0.build(Method::POST, "/topics");
-        let response = self.0.send(request.json(&topic)).await?;
-        let content = response.json::<CreateTopicResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateTopicResponse>(request.json(&topic))
+            .await?;
 
         Ok(content)
     }
@@ -31,13 +34,11 @@ impl TopicsSvc {
     /// Retrieve a topic by its ID.
     ///
     /// <https://resend.com/docs/api-reference/topics/get-topic>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, topic_id: &str) -> Result<Topic> {
         let path = format!("/topics/{topic_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Topic>().await?;
+        let content = self.0.send_json::<Topic>(request).await?;
 
         Ok(content)
     }
@@ -45,7 +46,6 @@ impl TopicsSvc {
     /// Update an existing topic.
     ///
     /// <https://resend.com/docs/api-reference/topics/update-topic>
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         topic_id: &str,
@@ -54,8 +54,10 @@ impl TopicsSvc {
         let path = format!("/topics/{topic_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateTopicResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateTopicResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -63,13 +65,11 @@ impl TopicsSvc {
     /// Remove an existing topic.
     ///
     /// <https://resend.com/docs/api-reference/topics/delete-topic>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, topic_id: &str) -> Result<DeleteTopicResponse> {
         let path = format!("/topics/{topic_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DeleteTopicResponse>().await?;
+        let content = self.0.send_json::<DeleteTopicResponse>(request).await?;
 
         Ok(content)
     }
@@ -79,14 +79,19 @@ impl TopicsSvc {
     /// - Default limit: 20
     ///
     /// <https://resend.com/docs/api-reference/topics/list-topics>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Topic>> {
         let request = self.0.build(Method::GET, "/topics").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Topic>>().await?;
+        let content = self.0.send_json::<ListResponse<Topic>>(request).await?;
 
         Ok(content)
     }
+
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Topic> {
+        Paginator::new(Arc::clone(&self.0), "/topics".to_owned(), &list_opts)
+    }
 }
 
 #[allow(unreachable_pub)]
@@ -237,7 +242,6 @@ pub mod types {
 #[allow(clippy::needless_return)]
 mod test {
     use crate::types::Topic;
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -246,7 +250,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
 
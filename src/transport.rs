@@ -0,0 +1,334 @@
+//! Pluggable HTTP transport used by [`Config::send`].
+//!
+//! Every request made by a [`Resend`] client is built with `reqwest` but dispatched through a
+//! [`Transport`]. By default that is the `reqwest` client itself, but any other implementation
+//! (a `hyper` client, a wasm `fetch` client, an in-memory fake for tests, ...) can be set with
+//! [`ConfigBuilder::transport`].
+//!
+//! ### Example
+//!
+//! An in-memory fake that answers every request with the same email:
+//!
+//! ```rust
+//! # {
+//! use resend_rs::transport::Transport;
+//! use resend_rs::{ConfigBuilder, Resend, Result};
+//!
+//! #[derive(Debug)]
+//! struct FakeTransport;
+//!
+//! #[async_trait::async_trait]
+//! impl Transport for FakeTransport {
+//!     async fn execute(&self, _request: reqwest::Request) -> Result<reqwest::Response> {
+//!         let response = http::Response::builder()
+//!             .status(200)
+//!             .header("content-type", "application/json")
+//!             .body(r#"{"id":"49a3999c-0ce1-4ea6-ab68-afcd6dc2e794"}"#)
+//!             .expect("valid response");
+//!
+//!         Ok(reqwest::Response::from(response))
+//!     }
+//! }
+//!
+//! let config = ConfigBuilder::new("re_...").transport(FakeTransport).build();
+//! let resend = Resend::with_config(config);
+//! # }
+//! ```
+//!
+//! ### Tower
+//!
+//! With the `tower` feature, requests can be dispatched through any
+//! [`tower::Service`](https://docs.rs/tower/latest/tower/trait.Service.html) with
+//! `ConfigBuilder::service`, e.g. a middleware stack on top of the `reqwest` client:
+//!
+//! ```rust
+//! # #[cfg(feature = "tower")]
+//! # {
+//! use std::time::Duration;
+//!
+//! use resend_rs::{ConfigBuilder, Resend};
+//! use tower::ServiceBuilder;
+//!
+//! let service = ServiceBuilder::new()
+//!     .concurrency_limit(4)
+//!     .timeout(Duration::from_secs(10))
+//!     .service(reqwest::Client::new());
+//!
+//! let config = ConfigBuilder::new("re_...").service(service).build();
+//! let resend = Resend::with_config(config);
+//! # }
+//! ```
+//!
+//! [`Config::send`]: crate::Config::send
+
+use std::fmt;
+
+use reqwest::{Client, Request, Response};
+
+use crate::Result;
+
+#[cfg(any(doc, feature = "tower"))]
+use crate::ConfigBuilder;
+#[cfg(doc)]
+use crate::Resend;
+
+/// Executes a single HTTP [`Request`].
+///
+/// Implementations should only perform the request, rate limiting, retries and error handling
+/// are all done by the client. Connection errors and timeouts should be returned as
+/// [`Error::Http`](crate::Error::Http) or [`Error::Transport`](crate::Error::Transport), so that
+/// they can be retried.
+///
+/// Async implementations are declared with [`async_trait`](https://docs.rs/async-trait).
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Executes the `request` and returns its response, regardless of the status code.
+    async fn execute(&self, request: Request) -> Result<Response>;
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Transport for Client {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        Ok(Self::execute(self, request).await?)
+    }
+}
+
+/// A [`Transport`] dispatching requests through a [`tower::Service`], see
+/// [`ConfigBuilder::service`].
+///
+/// Every request is sent with a clone of the service, wrap services that are expensive to clone
+/// or not [`Clone`] in a `tower::buffer::Buffer`.
+#[cfg(feature = "tower")]
+pub struct ServiceTransport<S> {
+    service: std::sync::Mutex<S>,
+}
+
+#[cfg(feature = "tower")]
+impl<S> ServiceTransport<S> {
+    /// Creates a new [`ServiceTransport`] dispatching requests through `service`.
+    pub const fn new(service: S) -> Self {
+        Self {
+            service: std::sync::Mutex::new(service),
+        }
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<S> fmt::Debug for ServiceTransport<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceTransport").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "tower")]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl<S> Transport for ServiceTransport<S>
+where
+    S: tower::Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Error: Into<tower::BoxError>,
+    S::Future: Send,
+{
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let mut service = self
+            .service
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+
+        std::future::poll_fn(|cx| service.poll_ready(cx))
+            .await
+            .map_err(|e| service_error(e.into()))?;
+        service
+            .call(request)
+            .await
+            .map_err(|e| service_error(e.into()))
+    }
+}
+
+/// Converts an error of a [`tower::Service`] into an [`Error`](crate::Error), keeping
+/// `reqwest` and `resend_rs` errors as they are.
+///
+/// Timeouts and overloaded services are returned as the retryable
+/// [`Error::Transport`](crate::Error::Transport), like the timeouts of `reqwest`.
+#[cfg(feature = "tower")]
+fn service_error(error: tower::BoxError) -> crate::Error {
+    let error = match error.downcast::<reqwest::Error>() {
+        Ok(error) => return (*error).into(),
+        Err(error) => error,
+    };
+
+    let error = match error.downcast::<crate::Error>() {
+        Ok(error) => return *error,
+        Err(error) => error,
+    };
+
+    if error.is::<tower::timeout::error::Elapsed>()
+        || error.is::<tower::load_shed::error::Overloaded>()
+    {
+        crate::Error::Transport {
+            source: error,
+            context: Box::default(),
+        }
+    } else {
+        crate::Error::other(format!("tower service error: {error}"))
+    }
+}
+
+#[cfg(feature = "tower")]
+impl ConfigBuilder {
+    /// Set a [`tower::Service`] that dispatches the requests, see [`ServiceTransport`].
+    ///
+    /// This is a shortcut for `.transport(ServiceTransport::new(service))`. Timeouts
+    /// (`tower::timeout::error::Elapsed`) and overloaded services
+    /// (`tower::load_shed::error::Overloaded`) are returned as
+    /// [`Error::Transport`](crate::Error::Transport) and retried like the timeouts of `reqwest`,
+    /// other errors of the middleware as [`Error::Other`](crate::Error::Other).
+    #[must_use]
+    pub fn service<S>(self, service: S) -> Self
+    where
+        S: tower::Service<Request, Response = Response> + Clone + Send + 'static,
+        S::Error: Into<tower::BoxError>,
+        S::Future: Send,
+    {
+        self.transport(ServiceTransport::new(service))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use crate::{
+        ConfigBuilder, Error, Resend,
+        rate_limit::RetryPolicy,
+        test::{ScriptedTransport, UNAVAILABLE, json_response},
+    };
+
+    /// Fails with a `503` `failures` times before returning a shared email.
+    fn failing(failures: usize) -> ScriptedTransport {
+        ScriptedTransport::new(move |call, request| {
+            assert_eq!(request.url().path(), "/emails/abc/share");
+            if call < failures {
+                return Ok(json_response(503, UNAVAILABLE));
+            }
+
+            Ok(json_response(
+                200,
+                r#"{"id":"abc","url":"https://resend.com/share/abc"}"#,
+            ))
+        })
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn custom_transport_with_retries() {
+        let policy = RetryPolicy::default()
+            .with_initial_backoff(std::time::Duration::from_millis(1))
+            .with_max_retries(2)
+            .with_retry_non_idempotent(true);
+
+        let flaky = failing(2);
+        let config = ConfigBuilder::new("re_test")
+            .transport(flaky.clone())
+            .retry_policy(policy)
+            .build();
+        let resend = Resend::with_config(config);
+
+        let shared = resend
+            .emails
+            .share("abc", crate::types::ShareEmailOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(shared.id.as_ref(), "abc");
+        assert_eq!(flaky.calls(), 3);
+
+        // Not enough retries
+        let flaky = failing(3);
+        let config = ConfigBuilder::new("re_test")
+            .transport(flaky.clone())
+            .retry_policy(policy)
+            .build();
+        let resend = Resend::with_config(config);
+
+        let res = resend
+            .emails
+            .share("abc", crate::types::ShareEmailOptions::new())
+            .await;
+        assert!(matches!(res, Err(Error::Resend { response, .. }) if response.status_code == 503));
+        assert_eq!(flaky.calls(), 3);
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    async fn post_without_idempotency_key_is_not_retried() {
+        let policy =
+            RetryPolicy::default().with_initial_backoff(std::time::Duration::from_millis(1));
+
+        let flaky = failing(1);
+        let config = ConfigBuilder::new("re_test")
+            .transport(flaky.clone())
+            .retry_policy(policy)
+            .build();
+        let resend = Resend::with_config(config);
+
+        let res = resend
+            .emails
+            .share("abc", crate::types::ShareEmailOptions::new())
+            .await;
+        assert!(matches!(res, Err(Error::Resend { response, .. }) if response.status_code == 503));
+        assert_eq!(flaky.calls(), 1);
+    }
+
+    #[tokio_shared_rt::test(shared = true)]
+    #[cfg(feature = "tower")]
+    async fn tower_service() {
+        use std::{
+            sync::{
+                Arc,
+                atomic::{AtomicU32, Ordering},
+            },
+            time::Duration,
+        };
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&calls);
+        let config = ConfigBuilder::new("re_test")
+            .service(
+                tower::ServiceBuilder::new()
+                    .concurrency_limit(1)
+                    .timeout(Duration::from_millis(50))
+                    .service_fn(move |request: reqwest::Request| {
+                        let calls = Arc::clone(&counter);
+                        async move {
+                            assert_eq!(request.url().path(), "/emails/abc/share");
+                            if calls.fetch_add(1, Ordering::SeqCst) > 0 {
+                                tokio::time::sleep(Duration::from_millis(200)).await;
+                            }
+                            let body = r#"{"id":"abc","url":"https://resend.com/share/abc"}"#;
+                            Ok::<_, Error>(json_response(200, body))
+                        }
+                    }),
+            )
+            .build();
+        let resend = Resend::with_config(config);
+
+        let shared = resend
+            .emails
+            .share("abc", crate::types::ShareEmailOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(shared.id.as_ref(), "abc");
+
+        // Errors of the middleware
+        let res = resend
+            .emails
+            .share("abc", crate::types::ShareEmailOptions::new())
+            .await;
+        let error = res.unwrap_err();
+        assert!(
+            matches!(error, Error::Transport { ref source, .. } if source.is::<tower::timeout::error::Elapsed>())
+        );
+        assert!(error.is_retryable());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
+use macro_rules_attribute::apply;
 use reqwest::Method;
 
 use crate::{
     Config, Result,
-    list_opts::{ListOptions, ListResponse},
+    list_opts::{ListOptions, ListResponse, Paginator},
     types::{
         CreateWebhookOptions, CreateWebhookResponse, DeleteWebhookResponse, UpdateWebhookOptions,
         UpdateWebhookResponse, Webhook,
@@ -15,15 +16,17 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct WebhookSvc(pub(crate) Arc<Config>);
 
+#[apply(crate::service_impl!)]
 impl WebhookSvc {
     /// Create a webhook to receive real-time notifications about email events.
     ///
     /// <https://resend.com/docs/api-reference/webhooks/create-webhook>
-    #[maybe_async::maybe_async]
     pub async fn create(&self, webhook: CreateWebhookOptions) -> Result<CreateWebhookResponse> {
         let request = self.0.build(Method::POST, "/webhooks");
-        let response = self.0.send(request.json(&webhook)).await?;
-        let content = response.json::<CreateWebhookResponse>().await?;
+        let content = self
+            .0
+            .send_json::<CreateWebhookResponse>(request.json(&webhook))
+            .await?;
 
         Ok(content)
     }
@@ -31,19 +34,16 @@ impl WebhookSvc {
     /// Retrieve a single webhook for the authenticated user.
     ///
     /// <https://resend.com/docs/api-reference/webhooks/get-webhook>
-    #[maybe_async::maybe_async]
     pub async fn get(&self, webhook_id: &str) -> Result<Webhook> {
         let path = format!("/webhooks/{webhook_id}");
 
         let request = self.0.build(Method::GET, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<Webhook>().await?;
+        let content = self.0.send_json::<Webhook>(request).await?;
 
         Ok(content)
     }
 
     /// Update an existing webhook configuration.
-    #[maybe_async::maybe_async]
     pub async fn update(
         &self,
         webhook_id: &str,
@@ -52,8 +52,10 @@ impl WebhookSvc {
         let path = format!("/webhooks/{webhook_id}");
 
         let request = self.0.build(Method::PATCH, &path);
-        let response = self.0.send(request.json(&update)).await?;
-        let content = response.json::<UpdateWebhookResponse>().await?;
+        let content = self
+            .0
+            .send_json::<UpdateWebhookResponse>(request.json(&update))
+            .await?;
 
         Ok(content)
     }
@@ -61,25 +63,28 @@ impl WebhookSvc {
     /// Retrieve a list of webhooks for the authenticated user.
     ///
     /// <https://resend.com/docs/api-reference/webhooks/list-webhooks>
-    #[maybe_async::maybe_async]
     pub async fn list<T>(&self, list_opts: ListOptions<T>) -> Result<ListResponse<Webhook>> {
         let request = self.0.build(Method::GET, "/webhooks").query(&list_opts);
-        let response = self.0.send(request).await?;
-        let content = response.json::<ListResponse<Webhook>>().await?;
+        let content = self.0.send_json::<ListResponse<Webhook>>(request).await?;
 
         Ok(content)
     }
 
+    /// Retrieves the items of every page of [`Self::list`], fetching the pages lazily.
+    ///
+    /// See [`Paginator`] for details.
+    pub fn list_all<T>(&self, list_opts: ListOptions<T>) -> Paginator<Webhook> {
+        Paginator::new(Arc::clone(&self.0), "/webhooks".to_owned(), &list_opts)
+    }
+
     /// Remove an existing webhook.
     ///
     /// <https://resend.com/docs/api-reference/webhooks/delete-webhook>
-    #[maybe_async::maybe_async]
     pub async fn delete(&self, webhook_id: &str) -> Result<bool> {
         let path = format!("/webhooks/{webhook_id}");
 
         let request = self.0.build(Method::DELETE, &path);
-        let response = self.0.send(request).await?;
-        let content = response.json::<DeleteWebhookResponse>().await?;
+        let content = self.0.send_json::<DeleteWebhookResponse>(request).await?;
 
         Ok(content.deleted)
     }
@@ -194,7 +199,6 @@ mod test {
         types::Webhook,
         types::{CreateWebhookOptions, CreateWebhookResponse},
     };
-    #[cfg(not(feature = "blocking"))]
     use crate::{
         list_opts::ListOptions,
         test::{CLIENT, DebugResult},
@@ -203,7 +207,6 @@ mod test {
 
     #[tokio_shared_rt::test(shared = true)]
     #[serial_test::serial]
-    #[cfg(not(feature = "blocking"))]
     async fn all() -> DebugResult<()> {
         let resend = &*CLIENT;
 